@@ -1,5 +1,9 @@
 use async_trait::async_trait;
-use vaultrs::{api::AuthInfo, client::Client, error::ClientError};
+use vaultrs::{
+    api::{AuthInfo, SecretString},
+    client::Client,
+    error::ClientError,
+};
 
 use crate::LoginMethod;
 
@@ -7,14 +11,14 @@ use crate::LoginMethod;
 #[derive(Debug)]
 pub struct AppRoleLogin {
     pub role_id: String,
-    pub secret_id: String,
+    pub secret_id: SecretString,
 }
 
 impl AppRoleLogin {
     pub fn new(role_id: &str, secret_id: &str) -> Self {
         AppRoleLogin {
             role_id: role_id.to_string(),
-            secret_id: secret_id.to_string(),
+            secret_id: secret_id.into(),
         }
     }
 }
@@ -26,7 +30,7 @@ impl LoginMethod for AppRoleLogin {
             client,
             mount,
             self.role_id.as_str(),
-            self.secret_id.as_str(),
+            self.secret_id.expose(),
         )
         .await
     }
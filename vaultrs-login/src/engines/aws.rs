@@ -5,7 +5,11 @@ use aws_credential_types::Credentials;
 use aws_smithy_runtime_api::client::identity::Identity;
 use base64::{engine::general_purpose, Engine as _};
 use std::time::SystemTime;
-use vaultrs::{api::AuthInfo, client::Client, error::ClientError};
+use vaultrs::{
+    api::{AuthInfo, SecretString},
+    client::Client,
+    error::ClientError,
+};
 
 use crate::LoginMethod;
 use aws_sigv4::http_request::{sign, SignableRequest, SigningSettings};
@@ -15,8 +19,8 @@ use aws_sigv4::sign::v4;
 #[derive(Debug)]
 pub struct AwsIamLogin {
     pub access_key: String,
-    pub secret_key: String,
-    pub session_token: Option<String>,
+    pub secret_key: SecretString,
+    pub session_token: Option<SecretString>,
     pub region: String,
     pub role: Option<String>,
     pub header_value: Option<String>,
@@ -53,8 +57,8 @@ impl LoginMethod for AwsIamLogin {
         let identity = Identity::new(
             Credentials::new(
                 &self.access_key,
-                &self.secret_key,
-                self.session_token.clone(),
+                self.secret_key.expose(),
+                self.session_token.as_ref().map(|t| t.expose().to_string()),
                 None,
                 "hardcoded-credentials",
             ),
@@ -1,12 +1,16 @@
 use async_trait::async_trait;
-use vaultrs::{api::AuthInfo, client::Client, error::ClientError};
+use vaultrs::{
+    api::{AuthInfo, SecretString},
+    client::Client,
+    error::ClientError,
+};
 
 use crate::LoginMethod;
 
 /// A login method which uses user/pass credentials for obtaining a new token.
 #[derive(Debug)]
 pub struct UserpassLogin {
-    pub password: String,
+    pub password: SecretString,
     pub username: String,
 }
 
@@ -14,7 +18,7 @@ impl UserpassLogin {
     pub fn new(username: &str, password: &str) -> Self {
         UserpassLogin {
             username: username.to_string(),
-            password: password.to_string(),
+            password: password.into(),
         }
     }
 }
@@ -26,7 +30,7 @@ impl LoginMethod for UserpassLogin {
             client,
             mount,
             self.username.as_str(),
-            self.password.as_str(),
+            self.password.expose(),
         )
         .await
     }
@@ -14,6 +14,7 @@
 //! ## Usage
 //!
 //! ```rust
+//! use vaultrs::api::SecretString;
 //! use vaultrs::client::{VaultClient, VaultClientSettingsBuilder};
 //! use vaultrs_login::LoginClient;
 //! use vaultrs_login::engines::approle::AppRoleLogin;
@@ -28,7 +29,7 @@
 //!
 //! // Use one of the login flows to obtain a token for the client
 //! let role_id = String::from("my-role-id");
-//! let secret_id = String::from("secret");
+//! let secret_id = SecretString::from("secret");
 //! let login = AppRoleLogin { role_id, secret_id };
 //!
 //! # tokio_test::block_on(async {
@@ -44,12 +45,14 @@ extern crate tracing;
 use async_trait::async_trait;
 use vaultrs::{
     api::AuthInfo,
-    client::{Client, VaultClient},
+    client::{token_helper::TokenHelper, Client, VaultClient},
     error::ClientError,
 };
 
 pub mod engines;
 pub mod method;
+#[cfg(feature = "watcher")]
+pub mod watcher;
 
 /// Represents a method for logging into Vault which returns a new token.
 #[async_trait]
@@ -88,7 +91,42 @@ pub trait LoginClient: Client + Sized {
         method: &M,
     ) -> Result<(), ClientError> {
         let info = method.login(self, mount).await?;
-        self.set_token(info.client_token.as_str());
+        self.set_token(info.client_token.expose());
+        Ok(())
+    }
+
+    /// Performs a login using the given method and swaps the token of this
+    /// client for the resulting one through a shared reference. This allows
+    /// logging in again while the client is used concurrently, e.g. behind an
+    /// [Arc][std::sync::Arc], see [Client::swap_token].
+    #[instrument(skip(self, method), err)]
+    /// Workaround until <https://github.com/tokio-rs/tracing/issues/2876> is fixed
+    #[allow(clippy::blocks_in_conditions)]
+    async fn login_shared<M: 'static + LoginMethod>(
+        &self,
+        mount: &str,
+        method: &M,
+    ) -> Result<AuthInfo, ClientError> {
+        let info = method.login(self, mount).await?;
+        self.swap_token(info.client_token.expose());
+        Ok(info)
+    }
+
+    /// Performs a login using the given method, sets the resulting token to
+    /// this client and persists it using the given [TokenHelper] so that it
+    /// can be picked up by later runs or by the Vault CLI.
+    #[instrument(skip(self, method, helper), err)]
+    /// Workaround until <https://github.com/tokio-rs/tracing/issues/2876> is fixed
+    #[allow(clippy::blocks_in_conditions)]
+    async fn login_and_store<M: 'static + LoginMethod, H: TokenHelper>(
+        &mut self,
+        mount: &str,
+        method: &M,
+        helper: &H,
+    ) -> Result<(), ClientError> {
+        let info = method.login(self, mount).await?;
+        helper.store(info.client_token.expose())?;
+        self.set_token(info.client_token.expose());
         Ok(())
     }
 
@@ -117,7 +155,7 @@ pub trait LoginClient: Client + Sized {
         callback: C,
     ) -> Result<(), ClientError> {
         let info = callback.callback(self, mount).await?;
-        self.set_token(info.client_token.as_str());
+        self.set_token(info.client_token.expose());
         Ok(())
     }
 }
@@ -0,0 +1,356 @@
+//! Keeps the token of a client alive in the background.
+//!
+//! A [LifetimeWatcher] tracks the TTL of the token currently set on a client
+//! and renews it once a configurable fraction of the TTL has elapsed. When the
+//! token can no longer be renewed (because it isn't renewable or because it
+//! reached its max TTL) the watcher can obtain a new token through a
+//! [LoginMethod] and set it on the client.
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use tokio::sync::RwLock;
+//! use vaultrs::client::{VaultClient, VaultClientSettingsBuilder};
+//! use vaultrs_login::engines::approle::AppRoleLogin;
+//! use vaultrs_login::watcher::{LifetimeWatcher, WatcherEvent};
+//!
+//! let client = VaultClient::new(
+//!     VaultClientSettingsBuilder::default()
+//!         .address("https://127.0.0.1:8200")
+//!         .token("TOKEN")
+//!         .build()
+//!         .unwrap()
+//! ).unwrap();
+//! let client = Arc::new(RwLock::new(client));
+//!
+//! let login = AppRoleLogin::new("my-role-id", "secret");
+//! # tokio_test::block_on(async {
+//! let mut handle = LifetimeWatcher::new(client.clone())
+//!     .with_login("approle", login)
+//!     .start();
+//!
+//! while let Some(event) = handle.next_event().await {
+//!     if let WatcherEvent::Expired = event {
+//!         break;
+//!     }
+//! }
+//! # })
+//! ```
+
+use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
+
+use tokio::{
+    sync::{mpsc, oneshot, RwLock},
+    task::JoinHandle,
+    time::{sleep, Instant},
+};
+use vaultrs::{
+    api::AuthInfo,
+    client::{parse_duration, Client},
+    error::ClientError,
+};
+
+use crate::LoginMethod;
+
+type LoginFuture = Pin<Box<dyn Future<Output = Result<AuthInfo, ClientError>> + Send>>;
+type LoginFn<C> = Box<dyn Fn(Arc<RwLock<C>>) -> LoginFuture + Send + Sync>;
+
+/// Settings which control when a [LifetimeWatcher] renews a token.
+#[derive(Clone, Debug)]
+pub struct WatcherConfig {
+    /// The fraction of the remaining TTL after which a renewal is attempted.
+    /// Defaults to two thirds.
+    pub renew_fraction: f64,
+    /// The increment requested when renewing the token. Defaults to the
+    /// server-side default.
+    pub increment: Option<String>,
+    /// The delay between attempts after a renewal or login failed. Defaults
+    /// to 10 seconds.
+    pub retry_delay: Duration,
+}
+
+impl Default for WatcherConfig {
+    fn default() -> Self {
+        WatcherConfig {
+            renew_fraction: 2.0 / 3.0,
+            increment: None,
+            retry_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Events emitted by a running [LifetimeWatcher].
+#[derive(Debug)]
+pub enum WatcherEvent {
+    /// The token was renewed.
+    Renewed(AuthInfo),
+    /// The token can no longer be renewed, either because it isn't renewable
+    /// or because it reached its max TTL.
+    MaxTtlReached,
+    /// A new token was obtained through the configured login method and set
+    /// on the client.
+    LoggedIn(AuthInfo),
+    /// A renewal or login attempt failed. The watcher keeps retrying.
+    Error(ClientError),
+    /// The token expired and no login method is configured. The watcher
+    /// stops after emitting this event.
+    Expired,
+}
+
+/// Watches the lifetime of the token set on a client, renewing it and logging
+/// in again when needed.
+///
+/// The client is shared behind a [RwLock] so that a new token can be set on
+/// it while other tasks keep using it for requests.
+pub struct LifetimeWatcher<C: Client> {
+    client: Arc<RwLock<C>>,
+    config: WatcherConfig,
+    login: Option<LoginFn<C>>,
+}
+
+impl<C: Client + 'static> LifetimeWatcher<C> {
+    /// Creates a new [LifetimeWatcher] using the default [WatcherConfig].
+    pub fn new(client: Arc<RwLock<C>>) -> Self {
+        LifetimeWatcher {
+            client,
+            config: WatcherConfig::default(),
+            login: None,
+        }
+    }
+
+    /// Sets the [WatcherConfig] used by this watcher.
+    pub fn with_config(mut self, config: WatcherConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Sets the login method used to obtain a new token once the current one
+    /// can no longer be renewed.
+    pub fn with_login<M: 'static + LoginMethod>(mut self, mount: &str, method: M) -> Self {
+        let method = Arc::new(method);
+        let mount = mount.to_string();
+        self.login = Some(Box::new(move |client: Arc<RwLock<C>>| -> LoginFuture {
+            let method = method.clone();
+            let mount = mount.clone();
+            Box::pin(async move {
+                let client = client.read().await;
+                method.login(&*client, mount.as_str()).await
+            })
+        }));
+        self
+    }
+
+    /// Starts watching the token in a background task.
+    pub fn start(self) -> WatcherHandle {
+        let (events, receiver) = mpsc::unbounded_channel();
+        let (stop, stopped) = oneshot::channel();
+        let task = tokio::spawn(self.run(events, stopped));
+        WatcherHandle {
+            events: receiver,
+            stop: Some(stop),
+            task,
+        }
+    }
+
+    async fn run(
+        self,
+        events: mpsc::UnboundedSender<WatcherEvent>,
+        mut stop: oneshot::Receiver<()>,
+    ) {
+        // A failed lookup is only retried when there is no login method to
+        // obtain a new token with, otherwise the watcher logs in once the
+        // retry delay elapsed.
+        let mut state = loop {
+            let lookup = self.client.read().await.lookup().await;
+            match lookup {
+                Ok(info) if info.ttl == 0 => {
+                    debug!("Token does not expire, nothing to watch");
+                    return;
+                }
+                Ok(info) => {
+                    // Seed the granted TTL so a capped TTL is detected on the
+                    // first renewal already when renewing without an
+                    // increment.
+                    let granted = match info.creation_ttl {
+                        0 => info.ttl,
+                        ttl => ttl,
+                    };
+                    break TokenState {
+                        expires_at: Instant::now() + Duration::from_secs(info.ttl),
+                        granted: Some(Duration::from_secs(granted)),
+                        renewable: info.renewable.unwrap_or(false),
+                        retrying: false,
+                    };
+                }
+                Err(e) if self.login.is_some() => {
+                    let _ = events.send(WatcherEvent::Error(e));
+                    break TokenState {
+                        expires_at: Instant::now(),
+                        granted: None,
+                        renewable: false,
+                        retrying: true,
+                    };
+                }
+                Err(e) => {
+                    warn!("Failed looking up token: {}", e);
+                    let _ = events.send(WatcherEvent::Error(e));
+                    tokio::select! {
+                        _ = &mut stop => return,
+                        _ = sleep(self.config.retry_delay) => {},
+                    }
+                }
+            }
+        };
+        if !state.renewable && !state.retrying {
+            let _ = events.send(WatcherEvent::MaxTtlReached);
+        }
+
+        loop {
+            let (wait, step) = self.next_step(&state);
+            tokio::select! {
+                _ = &mut stop => return,
+                _ = sleep(wait) => {},
+            }
+
+            match step {
+                Step::Renew => {
+                    let result = self
+                        .client
+                        .read()
+                        .await
+                        .renew(self.config.increment.as_deref())
+                        .await;
+                    match result {
+                        Ok(info) => {
+                            let lease = Duration::from_secs(info.lease_duration);
+                            // Vault silently caps the TTL once the max TTL is
+                            // near, so a lease shorter than requested means we
+                            // reached it. Without an increment the token is
+                            // renewed for its previous TTL.
+                            let requested = self
+                                .config
+                                .increment
+                                .as_deref()
+                                .and_then(parse_duration)
+                                .or(state.granted);
+                            let capped = lease.is_zero() || requested.is_some_and(|r| lease < r);
+                            state = TokenState {
+                                expires_at: Instant::now() + lease,
+                                granted: Some(lease),
+                                renewable: info.renewable && !capped,
+                                retrying: false,
+                            };
+                            let _ = events.send(WatcherEvent::Renewed(info));
+                            if capped {
+                                let _ = events.send(WatcherEvent::MaxTtlReached);
+                            }
+                        }
+                        Err(e) => {
+                            warn!("Failed renewing token: {}", e);
+                            let _ = events.send(WatcherEvent::Error(e));
+                            state.retrying = true;
+                            if Instant::now() >= state.expires_at {
+                                state.renewable = false;
+                                let _ = events.send(WatcherEvent::MaxTtlReached);
+                            }
+                        }
+                    }
+                }
+                Step::Login => {
+                    let login = self.login.as_ref().unwrap();
+                    match login(self.client.clone()).await {
+                        Ok(info) => {
+                            self.client
+                                .write()
+                                .await
+                                .set_token(info.client_token.expose());
+                            if info.lease_duration == 0 {
+                                debug!("Token does not expire, nothing to watch");
+                                let _ = events.send(WatcherEvent::LoggedIn(info));
+                                return;
+                            }
+                            let lease = Duration::from_secs(info.lease_duration);
+                            state = TokenState {
+                                expires_at: Instant::now() + lease,
+                                granted: Some(lease),
+                                renewable: info.renewable,
+                                retrying: false,
+                            };
+                            let _ = events.send(WatcherEvent::LoggedIn(info));
+                            if !state.renewable {
+                                let _ = events.send(WatcherEvent::MaxTtlReached);
+                            }
+                        }
+                        Err(e) => {
+                            warn!("Failed logging in: {}", e);
+                            let _ = events.send(WatcherEvent::Error(e));
+                            state.retrying = true;
+                        }
+                    }
+                }
+                Step::Expire => {
+                    let _ = events.send(WatcherEvent::Expired);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Determines how long to wait and what to do once the wait is over.
+    fn next_step(&self, state: &TokenState) -> (Duration, Step) {
+        let remaining = state.expires_at.saturating_duration_since(Instant::now());
+        let step = if state.renewable {
+            Step::Renew
+        } else if self.login.is_some() {
+            Step::Login
+        } else {
+            return (remaining, Step::Expire);
+        };
+
+        let wait = match (state.retrying, step) {
+            (true, Step::Renew) => self.config.retry_delay.min(remaining),
+            (true, _) => self.config.retry_delay,
+            (false, _) => remaining.mul_f64(self.config.renew_fraction),
+        };
+        (wait, step)
+    }
+}
+
+/// A handle to a running [LifetimeWatcher].
+///
+/// Dropping the handle stops the watcher, use [WatcherHandle::stop] to also
+/// wait for it to finish.
+pub struct WatcherHandle {
+    events: mpsc::UnboundedReceiver<WatcherEvent>,
+    stop: Option<oneshot::Sender<()>>,
+    task: JoinHandle<()>,
+}
+
+impl WatcherHandle {
+    /// Waits for the next event emitted by the watcher. Returns [None] once
+    /// the watcher has stopped.
+    pub async fn next_event(&mut self) -> Option<WatcherEvent> {
+        self.events.recv().await
+    }
+
+    /// Stops the watcher and waits for it to finish.
+    pub async fn stop(mut self) {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        let _ = self.task.await;
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Step {
+    Renew,
+    Login,
+    Expire,
+}
+
+struct TokenState {
+    expires_at: Instant,
+    granted: Option<Duration>,
+    renewable: bool,
+    retrying: bool,
+}
@@ -57,7 +57,7 @@ pub async fn test_login(client: &impl Client, endpoint: &AppRoleEndpoint) {
         client,
         endpoint.path.as_str(),
         role_id.as_str(),
-        secret_id.as_str(),
+        secret_id.expose(),
     )
     .await
     .unwrap();
@@ -172,7 +172,7 @@ mod role {
             .await
             .unwrap();
 
-            (id.secret_id, id.secret_id_accessor)
+            (id.secret_id.expose().to_string(), id.secret_id_accessor)
         }
 
         pub async fn test_list(client: &impl Client, endpoint: &AppRoleEndpoint) {
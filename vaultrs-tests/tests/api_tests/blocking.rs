@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+use vaultrs::api::token::requests::CreateTokenRequest;
+use vaultrs::blocking::{kv2, sys, token, transit, Client, VaultClient};
+use vaultrs::client::VaultClientSettingsBuilder;
+use vaultrs::fake::{FakeVault, ROOT_TOKEN};
+
+#[tokio::test]
+async fn test_blocking_client() {
+    let vault = FakeVault::start().await.unwrap();
+    let address = vault.address();
+
+    // The blocking client runs its own runtime, so it's used off the runtime
+    // serving the fake server
+    tokio::task::spawn_blocking(move || {
+        let client = VaultClient::new(
+            VaultClientSettingsBuilder::default()
+                .address(address)
+                .token(ROOT_TOKEN)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        let secret = HashMap::from([("key", "value")]);
+        let meta = kv2::set(&client, "secret", "app/blocking", &secret).unwrap();
+        assert_eq!(meta.version, 1);
+        let read: HashMap<String, String> = kv2::read(&client, "secret", "app/blocking").unwrap();
+        assert_eq!(read["key"], "value");
+        assert_eq!(
+            kv2::list(&client, "secret", "app").unwrap(),
+            vec!["blocking"]
+        );
+
+        let health = sys::health(&client).unwrap();
+        assert!(!health.sealed);
+
+        let auth = token::new(
+            &client,
+            Some(CreateTokenRequest::builder().policies(vec!["default".into()])),
+        )
+        .unwrap();
+        let lookup = token::lookup(&client, &auth.client_token).unwrap();
+        assert_eq!(lookup.policies, vec!["default"]);
+        assert_eq!(client.lookup().unwrap().id, ROOT_TOKEN);
+
+        sys::mount::enable(&client, "transit", "transit", None).unwrap();
+        transit::key::create(&client, "transit", "blocking", None).unwrap();
+        let encrypted =
+            transit::data::encrypt(&client, "transit", "blocking", "aGVsbG8=", None).unwrap();
+        let decrypted =
+            transit::data::decrypt(&client, "transit", "blocking", &encrypted.ciphertext, None)
+                .unwrap();
+        assert_eq!(decrypted.plaintext, "aGVsbG8=");
+    })
+    .await
+    .unwrap();
+}
@@ -1,9 +1,24 @@
 use std::env;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use reqwest::Url;
-use vaultrs::client::VaultClient;
+use rustify::enums::RequestMethod;
+use rustify::errors::ClientError as RestClientError;
+use vaultrs::api::auth::approle::requests::LoginWithApproleRequest;
+use vaultrs::api::SecretString;
+use vaultrs::client::consistency::{Consistency, InconsistentPolicy};
+use vaultrs::client::failover::NodeStatus;
+use vaultrs::client::middleware::{Headers, Middleware};
+use vaultrs::client::rate_limit::RateLimit;
+use vaultrs::client::retry::RetryPolicy;
+use vaultrs::client::token_helper::{
+    ExternalTokenHelper, FileTokenHelper, TokenHelper, TokenHelperChain,
+};
 use vaultrs::client::VaultClientSettingsBuilder;
+use vaultrs::client::{Client, VaultClient};
+use vaultrs::error::{ApiErrorKind, ClientError};
+use vaultrs::fake::FakeVault;
 
 #[test]
 fn build_without_token() {
@@ -23,27 +38,29 @@ fn build_with_invalid_address_panics() {
 
 #[test]
 fn build_without_address() {
-    let expected_address = "https://example.com:1234";
-    env::set_var("VAULT_ADDR", expected_address);
+    serialized(|| {
+        let expected_address = "https://example.com:1234";
+        env::set_var("VAULT_ADDR", expected_address);
 
-    let settings = VaultClientSettingsBuilder::default().build().unwrap();
-    assert_eq!(Url::parse(expected_address).unwrap(), settings.address);
+        let settings = VaultClientSettingsBuilder::default().build().unwrap();
+        assert_eq!(Url::parse(expected_address).unwrap(), settings.address);
 
-    // What follows should, ideally, be a separate test case.
-    // However, since we're using environment variables here
-    // and those are a shared resource for the whole process,
-    // (and tests are executed in parallel, in multiple threads),
-    // this can lead to race conditions.
-    // Since both cases test related behaviour, it's probably the simplest
-    // solution to just test them this way.
-    env::remove_var("VAULT_ADDR");
+        // What follows should, ideally, be a separate test case.
+        // However, since we're using environment variables here
+        // and those are a shared resource for the whole process,
+        // (and tests are executed in parallel, in multiple threads),
+        // this can lead to race conditions.
+        // Since both cases test related behaviour, it's probably the simplest
+        // solution to just test them this way.
+        env::remove_var("VAULT_ADDR");
 
-    let settings = VaultClientSettingsBuilder::default().build().unwrap();
+        let settings = VaultClientSettingsBuilder::default().build().unwrap();
 
-    assert_eq!(
-        Url::parse("http://127.0.0.1:8200").unwrap(),
-        settings.address
-    );
+        assert_eq!(
+            Url::parse("http://127.0.0.1:8200").unwrap(),
+            settings.address
+        );
+    });
 }
 
 #[test]
@@ -70,6 +87,33 @@ fn build_with_invalid_proxy_panics() {
         .build();
 }
 
+#[test]
+fn debug_redacts_secrets() {
+    let settings = VaultClientSettingsBuilder::default()
+        .address("https://127.0.0.1:9999")
+        .token("supersecret")
+        .build()
+        .unwrap();
+    assert!(!format!("{settings:?}").contains("supersecret"));
+
+    let client = VaultClient::new(settings).unwrap();
+    assert!(!format!("{:?}", client.middle()).contains("supersecret"));
+    assert_eq!(client.token(), "supersecret");
+
+    let request = LoginWithApproleRequest::builder()
+        .mount("approle")
+        .role_id("role")
+        .secret_id("supersecret")
+        .build()
+        .unwrap();
+    assert!(!format!("{request:?}").contains("supersecret"));
+    assert_eq!(request.secret_id, "supersecret");
+    assert_eq!(
+        serde_json::to_string(&SecretString::from("supersecret")).unwrap(),
+        "\"supersecret\""
+    );
+}
+
 const VAULT_SKIP_VERIFY: &str = "VAULT_SKIP_VERIFY";
 
 fn build_client() -> VaultClient {
@@ -113,6 +157,61 @@ fn test_should_verify_tls_if_variable_is_not_set() {
     });
 }
 
+#[test]
+fn test_max_retries_from_env() {
+    serialized(|| {
+        env::set_var("VAULT_MAX_RETRIES", "5");
+        let client = build_client();
+        assert_eq!(client.settings.retry.max_retries, 5);
+
+        env::remove_var("VAULT_MAX_RETRIES");
+        let client = build_client();
+        assert_eq!(client.settings.retry.max_retries, 2);
+    });
+}
+
+#[test]
+fn test_retry_policy_respects_idempotency() {
+    let policy = RetryPolicy::builder().max_retries(3u32).build().unwrap();
+    let unavailable = || RestClientError::ServerResponseError {
+        code: 503,
+        content: None,
+    };
+    let rate_limited = || RestClientError::ServerResponseError {
+        code: 429,
+        content: None,
+    };
+    let not_found = || RestClientError::ServerResponseError {
+        code: 404,
+        content: None,
+    };
+
+    assert!(policy.should_retry(&RequestMethod::GET, &unavailable()));
+    assert!(policy.should_retry(&RequestMethod::LIST, &rate_limited()));
+    assert!(!policy.should_retry(&RequestMethod::GET, &not_found()));
+
+    assert!(!policy.should_retry(&RequestMethod::POST, &unavailable()));
+    assert!(policy.should_retry(&RequestMethod::POST, &rate_limited()));
+
+    let policy = RetryPolicy::builder().non_idempotent(true).build().unwrap();
+    assert!(policy.should_retry(&RequestMethod::PUT, &unavailable()));
+}
+
+#[test]
+fn test_retry_policy_backoff_is_bounded() {
+    let policy = RetryPolicy::builder()
+        .min_backoff(Duration::from_millis(100))
+        .max_backoff(Duration::from_millis(300))
+        .build()
+        .unwrap();
+
+    for retry in 0..10 {
+        let delay = policy.backoff(retry);
+        assert!(delay >= Duration::from_millis(50));
+        assert!(delay <= Duration::from_millis(300));
+    }
+}
+
 /// Approximates `#[serial]` from the `serial_test` crate.
 ///
 /// No attempt is made to recover from a poisoned mutex, which will
@@ -128,3 +227,483 @@ fn serialized(f: impl FnOnce()) {
 
     f()
 }
+
+#[test]
+fn test_file_token_helper() {
+    let dir = tempfile::tempdir().unwrap();
+    let helper = FileTokenHelper::new(dir.path().join(".vault-token"));
+    assert_eq!(helper.get().unwrap(), None);
+
+    helper.store("my-token").unwrap();
+    assert_eq!(helper.get().unwrap().as_deref(), Some("my-token"));
+
+    let settings = VaultClientSettingsBuilder::default()
+        .address("https://127.0.0.1:8200")
+        .token_helper(&helper)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(settings.token, "my-token");
+
+    helper.erase().unwrap();
+    assert_eq!(helper.get().unwrap(), None);
+    helper.erase().unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_file_token_helper_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    // An existing world-readable file is restricted before storing the token
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(".vault-token");
+    std::fs::write(&path, "old-token").unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+    FileTokenHelper::new(&path).store("my-token").unwrap();
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_external_token_helper() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let token_path = dir.path().join("token");
+    let program = dir.path().join("helper.sh");
+    std::fs::write(
+        &program,
+        format!(
+            "#!/bin/sh\ncase $1 in\n  get) cat {0} 2>/dev/null || true ;;\n  store) cat > {0} ;;\n  erase) rm -f {0} ;;\n  *) exit 1 ;;\nesac\n",
+            token_path.display()
+        ),
+    )
+    .unwrap();
+    std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let helper = ExternalTokenHelper::new(&program);
+    assert_eq!(helper.get().unwrap(), None);
+    helper.store("my-token").unwrap();
+    assert_eq!(helper.get().unwrap().as_deref(), Some("my-token"));
+    helper.erase().unwrap();
+    assert_eq!(helper.get().unwrap(), None);
+
+    let helper = ExternalTokenHelper::new(dir.path().join("missing.sh"));
+    assert!(helper.get().is_err());
+}
+
+#[test]
+fn test_token_helper_chain() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = FileTokenHelper::new(dir.path().join("first"));
+    let second = FileTokenHelper::new(dir.path().join("second"));
+    second.store("second-token").unwrap();
+
+    let chain = TokenHelperChain::new(vec![Box::new(first.clone()), Box::new(second.clone())]);
+    assert_eq!(chain.get().unwrap().as_deref(), Some("second-token"));
+
+    chain.store("new-token").unwrap();
+    assert_eq!(first.get().unwrap().as_deref(), Some("new-token"));
+    assert_eq!(chain.get().unwrap().as_deref(), Some("new-token"));
+
+    chain.erase().unwrap();
+    assert_eq!(chain.get().unwrap(), None);
+}
+
+#[test]
+fn test_settings_from_env() {
+    serialized(|| {
+        env::set_var("VAULT_ADDR", "https://vault.example.com:8200");
+        env::set_var("VAULT_AGENT_ADDR", "http://127.0.0.1:8100");
+        env::set_var("VAULT_NAMESPACE", "ns1");
+        env::set_var("VAULT_CLIENT_TIMEOUT", "90s");
+        env::set_var("VAULT_TLS_SERVER_NAME", "vault.internal");
+        env::set_var("VAULT_WRAP_TTL", "5m");
+        env::set_var("VAULT_HTTP_PROXY", "http://proxy.example.com:3128");
+        env::set_var("VAULT_RATE_LIMIT", "10:20");
+
+        let settings = VaultClientSettingsBuilder::default().build().unwrap();
+        assert_eq!(
+            Url::parse("http://127.0.0.1:8100").unwrap(),
+            settings.address
+        );
+        assert_eq!(settings.namespace.as_deref(), Some("ns1"));
+        assert_eq!(settings.timeout, Some(Duration::from_secs(90)));
+        assert_eq!(settings.tls_server_name.as_deref(), Some("vault.internal"));
+        assert_eq!(settings.wrap_ttl, "5m");
+        assert_eq!(
+            Url::parse("http://proxy.example.com:3128").unwrap(),
+            settings.proxy.unwrap()
+        );
+        let limit = settings.rate_limit.unwrap();
+        assert_eq!(limit.rate(), 10.0);
+        assert_eq!(limit.burst(), 20);
+
+        // VAULT_PROXY_ADDR takes precedence over VAULT_HTTP_PROXY
+        env::set_var("VAULT_PROXY_ADDR", "http://other.example.com:3128");
+        env::set_var("VAULT_CLIENT_TIMEOUT", "1m30s");
+        env::remove_var("VAULT_AGENT_ADDR");
+        let settings = VaultClientSettingsBuilder::default().build().unwrap();
+        assert_eq!(
+            Url::parse("https://vault.example.com:8200").unwrap(),
+            settings.address
+        );
+        assert_eq!(settings.timeout, Some(Duration::from_secs(90)));
+        assert_eq!(
+            Url::parse("http://other.example.com:3128").unwrap(),
+            settings.proxy.unwrap()
+        );
+
+        for var in [
+            "VAULT_ADDR",
+            "VAULT_NAMESPACE",
+            "VAULT_CLIENT_TIMEOUT",
+            "VAULT_TLS_SERVER_NAME",
+            "VAULT_WRAP_TTL",
+            "VAULT_HTTP_PROXY",
+            "VAULT_PROXY_ADDR",
+            "VAULT_RATE_LIMIT",
+        ] {
+            env::remove_var(var);
+        }
+        let settings = VaultClientSettingsBuilder::default().build().unwrap();
+        assert_eq!(settings.namespace, None);
+        assert_eq!(settings.timeout, None);
+        assert_eq!(settings.wrap_ttl, "10m");
+        assert!(settings.proxy.is_none());
+        assert!(settings.rate_limit.is_none());
+    });
+}
+
+#[test]
+fn test_api_error_kind() {
+    let err = |code, error: &str| ClientError::APIError {
+        code,
+        errors: vec![error.to_string()],
+    };
+    assert!(err(503, "Vault is sealed").is_sealed());
+    assert!(err(503, "server is in maintenance").is_unavailable());
+    assert!(err(400, "check-and-set parameter did not match").is_cas_mismatch());
+    assert_eq!(err(404, "").kind(), Some(ApiErrorKind::NotFound));
+    assert_eq!(ClientError::WrapInvalidError.kind(), None);
+}
+
+#[tokio::test]
+async fn test_empty_result_error_kind() {
+    // Endpoints returning an empty result classify errors like the others
+    let server = FakeVault::start().await.unwrap();
+    let client = VaultClient::new(
+        VaultClientSettingsBuilder::default()
+            .address(server.address())
+            .token("invalid")
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    let err = vaultrs::token::tidy(&client).await.unwrap_err();
+    assert!(err.is_permission_denied());
+    assert_eq!(err.kind(), Some(ApiErrorKind::PermissionDenied));
+}
+
+#[tokio::test]
+async fn test_tls_server_name() {
+    // Requests are sent to the server name, which resolves to the address
+    let server = FakeVault::start().await.unwrap();
+    let settings = VaultClientSettingsBuilder::default()
+        .address(server.address())
+        .token(vaultrs::fake::ROOT_TOKEN)
+        .tls_server_name("vault.internal")
+        .build()
+        .unwrap();
+    let client = VaultClient::new(settings).unwrap();
+    assert!(client.http.base.starts_with("http://vault.internal:"));
+    client.lookup().await.unwrap();
+
+    let settings = VaultClientSettingsBuilder::default()
+        .addresses(["https://127.0.0.1:8200", "https://127.0.0.2:8200"])
+        .tls_server_name("vault.internal")
+        .build()
+        .unwrap();
+    assert!(matches!(
+        VaultClient::new(settings),
+        Err(ClientError::InvalidTlsServerName { .. })
+    ));
+
+    // A proxy would resolve the name itself
+    let settings = VaultClientSettingsBuilder::default()
+        .address("https://127.0.0.1:8200")
+        .proxy("http://127.0.0.1:3128")
+        .tls_server_name("vault.internal")
+        .build()
+        .unwrap();
+    assert!(matches!(
+        VaultClient::new(settings),
+        Err(ClientError::InvalidTlsServerName { reason, .. }) if reason.contains("proxy")
+    ));
+}
+
+#[tokio::test]
+async fn test_rate_limit() {
+    let limit = RateLimit::parse("20:2").unwrap();
+    let start = tokio::time::Instant::now();
+    for _ in 0..4 {
+        limit.acquire().await;
+    }
+    // The burst is allowed immediately, the rest waits for the rate
+    assert!(start.elapsed() >= Duration::from_millis(90));
+
+    assert!(RateLimit::parse("invalid").is_none());
+    assert!(RateLimit::parse("0").is_none());
+    assert_eq!(RateLimit::parse("5").unwrap().burst(), 5);
+}
+
+#[derive(Clone, Default)]
+struct Inspect {
+    request_ids: Arc<Mutex<Vec<String>>>,
+    responses: Arc<Mutex<Vec<u16>>>,
+}
+
+impl Middleware for Inspect {
+    fn request(&self, req: &mut http::Request<Vec<u8>>) -> Result<(), ClientError> {
+        let id = req.headers().get("X-Request-Id").unwrap();
+        self.request_ids
+            .lock()
+            .unwrap()
+            .push(id.to_str().unwrap().to_string());
+        Ok(())
+    }
+
+    fn response(&self, resp: &mut http::Response<Vec<u8>>) -> Result<(), ClientError> {
+        self.responses.lock().unwrap().push(resp.status().as_u16());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_middleware() {
+    let vault = FakeVault::start().await.unwrap();
+    let inspect = Inspect::default();
+    let client = VaultClient::new(
+        VaultClientSettingsBuilder::default()
+            .address(vault.address())
+            .token(vaultrs::fake::ROOT_TOKEN)
+            .middleware(Headers::default().with("X-Request-Id", "1234"))
+            .middleware(inspect.clone())
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+
+    // Middlewares run in order and see the headers added before them
+    vaultrs::sys::health(&client).await.unwrap();
+    assert_eq!(*inspect.request_ids.lock().unwrap(), vec!["1234"]);
+    assert_eq!(*inspect.responses.lock().unwrap(), vec![200]);
+
+    // Errors returned by a middleware abort the request
+    let client = VaultClient::new(
+        VaultClientSettingsBuilder::default()
+            .address(vault.address())
+            .middleware(
+                |_: &mut http::Request<Vec<u8>>| -> Result<(), ClientError> {
+                    Err(ClientError::MiddlewareError {
+                        message: "denied".to_string(),
+                    })
+                },
+            )
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    let err = vaultrs::sys::health(&client).await.unwrap_err();
+    assert!(matches!(err, ClientError::MiddlewareError { .. }));
+}
+
+#[tokio::test]
+async fn test_scoped_client() {
+    let vault = FakeVault::start().await.unwrap();
+    let headers: Arc<Mutex<Vec<http::HeaderMap>>> = Arc::default();
+    let seen = headers.clone();
+    let client = VaultClient::new(
+        VaultClientSettingsBuilder::default()
+            .address(vault.address())
+            .token(vaultrs::fake::ROOT_TOKEN)
+            .middleware(
+                move |req: &mut http::Request<Vec<u8>>| -> Result<(), ClientError> {
+                    seen.lock().unwrap().push(req.headers().clone());
+                    Ok(())
+                },
+            )
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+
+    // Overrides only apply to the requests sent through the view
+    let scoped = client.with_namespace("team-a").header("X-Team", "a");
+    vaultrs::token::lookup_self(&scoped).await.unwrap();
+    vaultrs::token::lookup_self(&client).await.unwrap();
+    {
+        let headers = headers.lock().unwrap();
+        assert_eq!(headers[0]["X-Vault-Namespace"], "team-a");
+        assert_eq!(headers[0]["X-Team"], "a");
+        assert!(headers[1].get("X-Vault-Namespace").is_none());
+        assert!(headers[1].get("X-Team").is_none());
+    }
+
+    // The token is shared with the parent unless overridden
+    let auth = vaultrs::token::new(&client, None).await.unwrap();
+    let scoped = client.scoped().token(&auth.client_token);
+    let lookup = vaultrs::token::lookup_self(&scoped).await.unwrap();
+    assert_eq!(lookup.id, auth.client_token);
+    let lookup = vaultrs::token::lookup_self(&client).await.unwrap();
+    assert_eq!(lookup.id, vaultrs::fake::ROOT_TOKEN);
+
+    // Setting a token on a view doesn't change the token of the parent
+    let mut scoped = client.scoped();
+    scoped.set_token(&auth.client_token);
+    let lookup = vaultrs::token::lookup_self(&scoped).await.unwrap();
+    assert_eq!(lookup.id, auth.client_token);
+    let lookup = vaultrs::token::lookup_self(&client).await.unwrap();
+    assert_eq!(lookup.id, vaultrs::fake::ROOT_TOKEN);
+
+    let scoped = client.scoped();
+    client.swap_token(&auth.client_token);
+    let lookup = vaultrs::token::lookup_self(&scoped).await.unwrap();
+    assert_eq!(lookup.id, auth.client_token);
+}
+
+#[tokio::test]
+async fn test_failover() {
+    let vault = FakeVault::start().await.unwrap();
+    let client = VaultClient::new(
+        VaultClientSettingsBuilder::default()
+            .addresses(["http://127.0.0.1:1", vault.address().as_str()])
+            .token(vaultrs::fake::ROOT_TOKEN)
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+
+    // Requests are sent to the node which is up
+    vaultrs::token::lookup_self(&client).await.unwrap();
+    let status = client.settings.failover.as_ref().unwrap().status();
+    assert_eq!(status[0].1, NodeStatus::Unreachable);
+    assert_eq!(status[1].1, NodeStatus::Active);
+}
+
+#[tokio::test]
+async fn test_failover_writes() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    // A node which reports itself as active but drops every other request
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let flaky = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut buf = [0; 4096];
+            let n = stream.read(&mut buf).await.unwrap_or(0);
+            if String::from_utf8_lossy(&buf[..n]).contains("/v1/sys/health") {
+                let _ = stream
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                    .await;
+            }
+        }
+    });
+    let vault = FakeVault::start().await.unwrap();
+    let client = || {
+        VaultClient::new(
+            VaultClientSettingsBuilder::default()
+                .addresses([flaky.as_str(), vault.address().as_str()])
+                .token(vaultrs::fake::ROOT_TOKEN)
+                .retry(RetryPolicy::none())
+                .build()
+                .unwrap(),
+        )
+        .unwrap()
+    };
+
+    // The node may have applied the write, so it isn't sent to another one
+    assert!(vaultrs::token::new(&client(), None).await.is_err());
+
+    // Reads are sent to another node
+    vaultrs::token::lookup_self(&client()).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_consistency() {
+    use base64::{engine::general_purpose, Engine as _};
+
+    let state = |cluster: &str, index: u64| {
+        general_purpose::STANDARD.encode(format!("v1:{cluster}:{index}:0:hmac"))
+    };
+
+    // Newer states replace older ones from the same cluster
+    let consistency = Consistency::new().inconsistent(InconsistentPolicy::ForwardActiveNode);
+    consistency.record(&state("a", 5));
+    consistency.record(&state("a", 3));
+    consistency.record(&state("b", 1));
+    assert_eq!(consistency.states(), vec![state("a", 5), state("b", 1)]);
+    consistency.record(&state("a", 8));
+    assert_eq!(consistency.states(), vec![state("a", 8), state("b", 1)]);
+
+    // The states are required on every request
+    let vault = FakeVault::start().await.unwrap();
+    let headers: Arc<Mutex<Vec<http::HeaderMap>>> = Arc::default();
+    let seen = headers.clone();
+    let client = VaultClient::new(
+        VaultClientSettingsBuilder::default()
+            .address(vault.address())
+            .token(vaultrs::fake::ROOT_TOKEN)
+            .consistency(consistency)
+            .middleware(
+                move |req: &mut http::Request<Vec<u8>>| -> Result<(), ClientError> {
+                    seen.lock().unwrap().push(req.headers().clone());
+                    Ok(())
+                },
+            )
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    vaultrs::token::lookup_self(&client).await.unwrap();
+
+    let headers = headers.lock().unwrap();
+    let indexes: Vec<_> = headers[0].get_all("X-Vault-Index").iter().collect();
+    assert_eq!(indexes, vec![&state("a", 8), &state("b", 1)]);
+    assert_eq!(headers[0]["X-Vault-Inconsistent"], "forward-active-node");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_unix_socket() {
+    let dir = tempfile::tempdir().unwrap();
+    let vault = FakeVault::start_unix(dir.path().join("agent.sock"))
+        .await
+        .unwrap();
+    assert!(vault.address().starts_with("unix:///"));
+
+    let client = vault.client();
+    let secret = std::collections::HashMap::from([("key", "value")]);
+    vaultrs::kv2::set(&client, "secret", "mysecret", &secret)
+        .await
+        .unwrap();
+    let read: std::collections::HashMap<String, String> =
+        vaultrs::kv2::read(&client, "secret", "mysecret")
+            .await
+            .unwrap();
+    assert_eq!(read["key"], "value");
+}
+
+#[cfg(unix)]
+#[test]
+fn build_with_unix_socket() {
+    let settings = VaultClientSettingsBuilder::default()
+        .address("unix:///run/vault/agent.sock")
+        .build()
+        .unwrap();
+    assert_eq!(settings.address.path(), "/run/vault/agent.sock");
+    assert!(VaultClient::new(settings).is_ok());
+}
@@ -1,6 +1,12 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::sync::RwLock;
 use tracing::debug;
 use vaultrs::api::database::requests::PostgreSQLConnectionRequest;
+use vaultrs::api::Secret;
 use vaultrs::client::Client;
+use vaultrs::fake::FakeVault;
+use vaultrs::lease_manager::{LeaseEvent, LeaseManager, LeaseManagerConfig};
 use vaultrs::sys::mount;
 
 use crate::common::{TestBuilder, POSTGRES_PASSWORD, POSTGRES_USER};
@@ -22,6 +28,8 @@ async fn test() {
             role::test_set(client, &endpoint).await;
             role::test_read(client, &endpoint).await;
             role::test_creds(client, &endpoint).await;
+            role::test_lease(client, &endpoint).await;
+            role::test_lease_manager(client, &endpoint).await;
             role::test_list(client, &endpoint).await;
             role::test_delete(client, &endpoint).await;
 
@@ -41,6 +49,37 @@ async fn test() {
         .await;
 }
 
+#[tokio::test]
+async fn test_lease_manager_rejected_renewal() {
+    let vault = FakeVault::start().await.unwrap();
+    let client = Arc::new(RwLock::new(vault.client()));
+    let manager = LeaseManager::new(client)
+        .with_config(LeaseManagerConfig {
+            renew_fraction: 0.0,
+            ..Default::default()
+        })
+        .start();
+    let mut events = manager.subscribe();
+
+    // The fake server doesn't know the lease, which isn't worth retrying
+    manager.register(&Secret {
+        data: (),
+        lease_id: "database/creds/test/abc".to_string(),
+        lease_duration: 3600,
+        renewable: true,
+        request_id: String::new(),
+        warnings: vec![],
+    });
+    let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    match event {
+        LeaseEvent::Expired { lease_id } => assert_eq!(lease_id, "database/creds/test/abc"),
+        event => panic!("unexpected event: {event:?}"),
+    }
+}
+
 mod connection {
     use super::{Client, DatabaseEndpoint};
     use vaultrs::database::connection;
@@ -81,12 +120,81 @@ mod connection {
 
 mod role {
     use super::{Client, DatabaseEndpoint};
-    use vaultrs::{api::database::requests::SetRoleRequest, database::role};
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+    use vaultrs::{
+        api::{database::requests::SetRoleRequest, sys::requests::RenewLeaseRequest},
+        client::VaultClient,
+        database::role,
+        lease_manager::{LeaseEvent, LeaseManager},
+        sys::lease,
+    };
 
     pub async fn test_creds(client: &impl Client, endpoint: &DatabaseEndpoint) {
         role::creds(client, endpoint.path.as_str(), endpoint.role.as_str())
             .await
             .unwrap();
+
+        let secret = role::creds_with_lease(client, endpoint.path.as_str(), endpoint.role.as_str())
+            .await
+            .unwrap();
+        assert!(!secret.lease_id.is_empty());
+        assert!(secret.lease_duration > 0);
+    }
+
+    pub async fn test_lease(client: &impl Client, endpoint: &DatabaseEndpoint) {
+        let secret = role::creds_with_lease(client, endpoint.path.as_str(), endpoint.role.as_str())
+            .await
+            .unwrap();
+
+        let lease = lease::read(client, secret.lease_id.as_str()).await.unwrap();
+        assert_eq!(lease.id, secret.lease_id);
+        assert!(lease.renewable);
+
+        let prefix = format!("{}/creds/{}/", endpoint.path, endpoint.role);
+        let leases = lease::list(client, prefix.as_str()).await.unwrap();
+        assert!(!leases.keys.is_empty());
+
+        let renewed = lease::renew(
+            client,
+            secret.lease_id.as_str(),
+            Some(RenewLeaseRequest::builder().increment("1h")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(renewed.lease_id, secret.lease_id);
+
+        lease::revoke(client, secret.lease_id.as_str(), None)
+            .await
+            .unwrap();
+        let res = lease::read(client, secret.lease_id.as_str()).await;
+        assert!(res.is_err());
+
+        lease::revoke_prefix(client, prefix.as_str(), None)
+            .await
+            .unwrap();
+        lease::revoke_force(client, prefix.as_str()).await.unwrap();
+        lease::tidy(client).await.unwrap();
+    }
+
+    pub async fn test_lease_manager(client: &impl Client, endpoint: &DatabaseEndpoint) {
+        let managed = VaultClient::new(client.settings().clone()).unwrap();
+        let managed = Arc::new(RwLock::new(managed));
+        let manager = LeaseManager::new(managed.clone()).start();
+        let mut events = manager.subscribe();
+
+        let secret = role::creds_with_lease(client, endpoint.path.as_str(), endpoint.role.as_str())
+            .await
+            .unwrap();
+        manager.register(&secret);
+        manager.shutdown().await;
+
+        match events.recv().await.unwrap() {
+            LeaseEvent::Revoked { lease_id } => assert_eq!(lease_id, secret.lease_id),
+            event => panic!("unexpected event: {event:?}"),
+        }
+        let res = lease::read(client, secret.lease_id.as_str()).await;
+        assert!(res.is_err());
     }
 
     pub async fn test_delete(client: &impl Client, endpoint: &DatabaseEndpoint) {
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use vaultrs::api::kv2::requests::SetSecretRequestOptions;
+use vaultrs::api::token::requests::CreateTokenRequest;
+use vaultrs::api::transit::requests::UpdateKeyConfigurationRequest;
+use vaultrs::api::transit::responses::ReadKeyData;
+use vaultrs::client::Client;
+use vaultrs::fake::{FakeVault, ROOT_TOKEN};
+use vaultrs::{cubbyhole, kv1, kv2, sys, token, transit};
+
+#[tokio::test]
+async fn test_kv2() {
+    let vault = FakeVault::start().await.unwrap();
+    let client = vault.client();
+
+    let secret = HashMap::from([("key", "value1")]);
+    let meta = kv2::set(&client, "secret", "app/config", &secret)
+        .await
+        .unwrap();
+    assert_eq!(meta.version, 1);
+
+    let secret = HashMap::from([("key", "value2")]);
+    kv2::set(&client, "secret", "app/config", &secret)
+        .await
+        .unwrap();
+
+    let read: HashMap<String, String> = kv2::read(&client, "secret", "app/config").await.unwrap();
+    assert_eq!(read["key"], "value2");
+    let read: HashMap<String, String> = kv2::read_version(&client, "secret", "app/config", 1)
+        .await
+        .unwrap();
+    assert_eq!(read["key"], "value1");
+
+    // A stale check-and-set version is rejected
+    let res = kv2::set_with_options(
+        &client,
+        "secret",
+        "app/config",
+        &secret,
+        SetSecretRequestOptions { cas: 1 },
+    )
+    .await;
+    assert!(res.is_err());
+
+    let keys = kv2::list(&client, "secret", "").await.unwrap();
+    assert_eq!(keys, vec!["app/"]);
+    let metadata = kv2::read_metadata(&client, "secret", "app/config")
+        .await
+        .unwrap();
+    assert_eq!(metadata.current_version, 2);
+
+    kv2::delete_latest(&client, "secret", "app/config")
+        .await
+        .unwrap();
+    let res = kv2::read::<HashMap<String, String>>(&client, "secret", "app/config").await;
+    assert!(res.unwrap_err().is_not_found());
+    kv2::undelete_versions(&client, "secret", "app/config", vec![2])
+        .await
+        .unwrap();
+    let read: HashMap<String, String> = kv2::read(&client, "secret", "app/config").await.unwrap();
+    assert_eq!(read["key"], "value2");
+
+    kv2::delete_metadata(&client, "secret", "app/config")
+        .await
+        .unwrap();
+    assert!(kv2::list(&client, "secret", "").await.is_err());
+}
+
+#[tokio::test]
+async fn test_kv1_and_cubbyhole() {
+    let vault = FakeVault::start().await.unwrap();
+    let client = vault.client();
+
+    sys::mount::enable(&client, "kv1", "kv", None)
+        .await
+        .unwrap();
+    assert!(sys::mount::list(&client)
+        .await
+        .unwrap()
+        .contains_key("kv1/"));
+
+    let secret = HashMap::from([("key", "value")]);
+    kv1::set(&client, "kv1", "mysecret", &secret).await.unwrap();
+    let read: HashMap<String, String> = kv1::get(&client, "kv1", "mysecret").await.unwrap();
+    assert_eq!(read["key"], "value");
+    kv1::delete(&client, "kv1", "mysecret").await.unwrap();
+    assert!(
+        kv1::get::<HashMap<String, String>>(&client, "kv1", "mysecret")
+            .await
+            .is_err()
+    );
+
+    // Cubbyhole secrets are only visible to the token which wrote them
+    cubbyhole::set(&client, "cubbyhole", "mysecret", &secret)
+        .await
+        .unwrap();
+    let other = vault.client();
+    let auth = token::new(&other, None).await.unwrap();
+    other.swap_token(&auth.client_token);
+    assert!(
+        cubbyhole::get::<HashMap<String, String>>(&other, "cubbyhole", "mysecret")
+            .await
+            .is_err()
+    );
+    let read: HashMap<String, String> = cubbyhole::get(&client, "cubbyhole", "mysecret")
+        .await
+        .unwrap();
+    assert_eq!(read["key"], "value");
+}
+
+#[tokio::test]
+async fn test_token() {
+    let vault = FakeVault::start().await.unwrap();
+    let client = vault.client();
+
+    let auth = token::new(
+        &client,
+        Some(
+            CreateTokenRequest::builder()
+                .policies(vec!["default".to_string()])
+                .ttl("1h"),
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(auth.lease_duration, 3600);
+
+    let lookup = token::lookup(&client, &auth.client_token).await.unwrap();
+    assert_eq!(lookup.policies, vec!["default"]);
+    let lookup = token::lookup_accessor(&client, &auth.accessor)
+        .await
+        .unwrap();
+    assert_eq!(lookup.accessor, auth.accessor);
+
+    token::revoke(&client, &auth.client_token).await.unwrap();
+    assert!(token::lookup(&client, &auth.client_token).await.is_err());
+
+    // Requests made with an unknown token are denied
+    client.swap_token("unknown");
+    assert!(token::lookup_self(&client).await.is_err());
+}
+
+#[tokio::test]
+async fn test_transit() {
+    let vault = FakeVault::start().await.unwrap();
+    let client = vault.client();
+
+    sys::mount::enable(&client, "transit", "transit", None)
+        .await
+        .unwrap();
+    transit::key::create(&client, "transit", "mykey", None)
+        .await
+        .unwrap();
+
+    let encrypted = transit::data::encrypt(&client, "transit", "mykey", "aGVsbG8=", None)
+        .await
+        .unwrap();
+    assert!(encrypted.ciphertext.starts_with("vault:v1:"));
+
+    transit::key::rotate(&client, "transit", "mykey")
+        .await
+        .unwrap();
+    let key = transit::key::read(&client, "transit", "mykey")
+        .await
+        .unwrap();
+    match key.keys {
+        ReadKeyData::Symmetric(versions) => assert_eq!(versions.len(), 2),
+        ReadKeyData::Asymmetric(_) => panic!("expected a symmetric key"),
+    }
+
+    let decrypted =
+        transit::data::decrypt(&client, "transit", "mykey", &encrypted.ciphertext, None)
+            .await
+            .unwrap();
+    assert_eq!(decrypted.plaintext, "aGVsbG8=");
+
+    // Keys can only be deleted once deletion is allowed
+    assert!(transit::key::delete(&client, "transit", "mykey")
+        .await
+        .is_err());
+    transit::key::update(
+        &client,
+        "transit",
+        "mykey",
+        Some(UpdateKeyConfigurationRequest::builder().deletion_allowed(true)),
+    )
+    .await
+    .unwrap();
+    transit::key::delete(&client, "transit", "mykey")
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_sys() {
+    let vault = FakeVault::start().await.unwrap();
+    let client = vault.client();
+
+    let health = sys::health(&client).await.unwrap();
+    assert!(!health.sealed);
+
+    sys::policy::set(
+        &client,
+        "mypolicy",
+        r#"path "secret/*" { capabilities = ["read"] }"#,
+    )
+    .await
+    .unwrap();
+    let policies = sys::policy::list(&client).await.unwrap();
+    assert!(policies.policies.contains(&"mypolicy".to_string()));
+    let policy = sys::policy::read(&client, "mypolicy").await.unwrap();
+    assert!(policy.rules.contains("secret/*"));
+    sys::policy::delete(&client, "mypolicy").await.unwrap();
+
+    sys::mount::enable(&client, "other", "kv-v2", None)
+        .await
+        .unwrap();
+    sys::mount::disable(&client, "other").await.unwrap();
+    assert!(!sys::mount::list(&client)
+        .await
+        .unwrap()
+        .contains_key("other/"));
+}
+
+#[tokio::test]
+async fn test_request_too_large() {
+    let vault = FakeVault::start().await.unwrap();
+
+    let res = reqwest::Client::new()
+        .post(format!("{}/v1/secret/data/large", vault.address()))
+        .header("X-Vault-Token", ROOT_TOKEN)
+        .body(vec![b' '; 33 * 1024 * 1024])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 413);
+}
@@ -53,8 +53,9 @@ async fn test_kv1() {
                 "Expected error when reading {} after delete.",
                 &secret_path
             )) {
-                ClientError::APIError { code, .. } => {
-                    assert_eq!(code, 404, "Expected error code 404 for non-existing secret")
+                e @ ClientError::APIError { code, .. } => {
+                    assert_eq!(code, 404, "Expected error code 404 for non-existing secret");
+                    assert!(e.is_not_found());
                 }
                 e => {
                     panic!("Expected error to be APIError with code 404, got {e:?}")
@@ -167,7 +167,7 @@ async fn test_set_with_compare_and_swap(client: &impl Client, endpoint: &SecretE
     )
     .await
     .unwrap();
-    kv2::set_with_options(
+    let err = kv2::set_with_options(
         client,
         endpoint.path.as_str(),
         "test-compare-and-swap",
@@ -176,6 +176,7 @@ async fn test_set_with_compare_and_swap(client: &impl Client, endpoint: &SecretE
     )
     .await
     .unwrap_err();
+    assert!(err.is_cas_mismatch(), "unexpected error: {err:?}");
 }
 
 async fn test_set_metadata(client: &impl Client, endpoint: &SecretEndpoint) {
@@ -0,0 +1,142 @@
+use std::sync::{Arc, Mutex};
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use vaultrs::api::logical::requests::LogicalRequest;
+use vaultrs::api::ResponseWrapper;
+use vaultrs::client::{Client, VaultClient, VaultClientSettingsBuilder};
+use vaultrs::fake::FakeVault;
+use vaultrs::logical;
+use vaultrs::sys::mount;
+
+use crate::common::TestBuilder;
+
+const MOUNT: &str = "logical_test";
+
+#[derive(Debug, Deserialize)]
+struct KvData {
+    data: TestSecret,
+}
+
+#[derive(Debug, Deserialize)]
+struct TestSecret {
+    key: String,
+    password: Option<String>,
+}
+
+#[tokio::test]
+async fn test() {
+    TestBuilder::new()
+        .check(|test| async move {
+            let client = test.client();
+            mount::enable(client, MOUNT, "kv-v2", None).await.unwrap();
+
+            test_write(client).await;
+            test_read(client).await;
+            test_patch(client).await;
+            test_list(client).await;
+            test_wrap(client).await;
+            test_delete(client).await;
+        })
+        .await;
+}
+
+async fn test_write(client: &impl Client) {
+    let res: Option<vaultrs::api::EndpointResult<Value>> = logical::write(
+        client,
+        &format!("{MOUNT}/data/test"),
+        &json!({ "data": { "key": "mykey", "password": "supersecret" } }),
+    )
+    .await
+    .unwrap();
+    let data = res.unwrap().data.unwrap();
+    assert_eq!(data["version"], 1);
+}
+
+async fn test_read(client: &impl Client) {
+    let res = client
+        .logical()
+        .read::<KvData>(&format!("{MOUNT}/data/test"))
+        .await
+        .unwrap();
+    assert!(!res.request_id.is_empty());
+    let secret = res.data.unwrap().data;
+    assert_eq!(secret.key, "mykey");
+    assert_eq!(secret.password.as_deref(), Some("supersecret"));
+}
+
+async fn test_patch(client: &impl Client) {
+    client
+        .logical()
+        .patch::<Value, _>(
+            &format!("{MOUNT}/data/test"),
+            &json!({ "data": { "password": null } }),
+        )
+        .await
+        .unwrap();
+
+    let res = client
+        .logical()
+        .read::<KvData>(&format!("{MOUNT}/data/test"))
+        .await
+        .unwrap();
+    let secret = res.data.unwrap().data;
+    assert_eq!(secret.key, "mykey");
+    assert!(secret.password.is_none());
+}
+
+async fn test_list(client: &impl Client) {
+    let res = logical::list::<Value>(client, &format!("{MOUNT}/metadata"))
+        .await
+        .unwrap();
+    assert_eq!(res.data.unwrap()["keys"], json!(["test"]));
+}
+
+async fn test_wrap(client: &impl Client) {
+    let endpoint = LogicalRequest::builder()
+        .path(format!("{MOUNT}/data/test"))
+        .method(rustify::enums::RequestMethod::GET)
+        .build()
+        .unwrap();
+    let wrapped = endpoint.wrap(client).await.unwrap();
+    let data = wrapped.unwrap(client).await.unwrap();
+    assert_eq!(data["data"]["key"], "mykey");
+}
+
+async fn test_delete(client: &impl Client) {
+    let res = logical::delete::<Value>(client, &format!("{MOUNT}/metadata/test"))
+        .await
+        .unwrap();
+    assert!(res.is_none());
+
+    let res = logical::read::<Value>(client, &format!("{MOUNT}/data/test")).await;
+    assert!(res.unwrap_err().is_not_found());
+}
+
+#[tokio::test]
+async fn test_patch_content_type() {
+    let vault = FakeVault::start().await.unwrap();
+    let content_type = Arc::new(Mutex::new(None));
+    let seen = content_type.clone();
+    let client = VaultClient::new(
+        VaultClientSettingsBuilder::default()
+            .address(vault.address())
+            .token(vaultrs::fake::ROOT_TOKEN)
+            .middleware(move |req: &mut http::Request<Vec<u8>>| {
+                *seen.lock().unwrap() = req
+                    .headers()
+                    .get(http::header::CONTENT_TYPE)
+                    .map(|v| v.to_str().unwrap().to_string());
+                Ok(())
+            })
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+
+    let _ = logical::patch::<Value, _>(&client, "secret/data/test", &json!({ "data": {} })).await;
+    assert_eq!(
+        content_type.lock().unwrap().as_deref(),
+        Some("application/merge-patch+json")
+    );
+}
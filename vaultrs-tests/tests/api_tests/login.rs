@@ -1,8 +1,10 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 use tracing::{debug, instrument};
 use vaultrs::api::auth::approle::requests::SetAppRoleRequest;
 use vaultrs::api::auth::userpass::requests::CreateUserRequest;
 use vaultrs::auth::{approle, userpass};
+use vaultrs::client::token_helper::{FileTokenHelper, TokenHelper};
 use vaultrs::client::{Client, VaultClient};
 use vaultrs::sys::auth;
 use vaultrs_login::engines::{approle::AppRoleLogin, userpass::UserpassLogin};
@@ -201,12 +203,55 @@ async fn test_userpass(client: &mut VaultClient) {
             "userpass_test",
             &UserpassLogin {
                 username: "test".to_string(),
-                password: "test".to_string(),
+                password: "test".into(),
             },
         )
         .await
         .unwrap();
     client.lookup().await.unwrap();
+
+    // Test login persisting the token
+    let dir = tempfile::tempdir().unwrap();
+    let helper = FileTokenHelper::new(dir.path().join(".vault-token"));
+    client
+        .login_and_store(
+            "userpass_test",
+            &UserpassLogin {
+                username: "test".to_string(),
+                password: "test".into(),
+            },
+            &helper,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        helper.get().unwrap().as_deref(),
+        Some(client.settings().token.expose())
+    );
+
+    // Test login swapping the token of a shared client
+    let shared = Arc::new(VaultClient::new(client.settings().clone()).unwrap());
+    let tasks: Vec<_> = (0..4)
+        .map(|_| {
+            let shared = shared.clone();
+            tokio::spawn(async move { shared.lookup().await.unwrap() })
+        })
+        .collect();
+    let info = shared
+        .login_shared(
+            "userpass_test",
+            &UserpassLogin {
+                username: "test".to_string(),
+                password: "test".into(),
+            },
+        )
+        .await
+        .unwrap();
+    for task in tasks {
+        task.await.unwrap();
+    }
+    assert_eq!(shared.token(), info.client_token);
+    assert_eq!(shared.lookup().await.unwrap().accessor, info.accessor);
 }
 
 #[instrument(skip(localstack_url, client))]
@@ -306,9 +351,9 @@ async fn test_aws(localstack_url: &str, client: &mut VaultClient) {
     // Test login
     let login = vaultrs_login::engines::aws::AwsIamLogin {
         access_key: assumed_role_credentials.access_key_id,
-        secret_key: assumed_role_credentials.secret_access_key,
+        secret_key: assumed_role_credentials.secret_access_key.into(),
         region: "local".to_string(),
-        session_token: Some(assumed_role_credentials.session_token),
+        session_token: Some(assumed_role_credentials.session_token.into()),
         role: Some("test_role".to_string()),
         header_value: None,
     };
@@ -1,22 +1,28 @@
 mod approle;
 mod aws;
+mod blocking;
 mod cert;
 mod client;
 mod common;
 mod cubbyhole;
 mod database;
+mod fake;
 mod identity;
 mod kubernetes;
 mod kv1;
 mod kv2;
+mod logical;
 mod login;
 mod oidc;
 mod pki;
+mod recording;
 mod ssh;
 mod sys;
 mod token;
 mod transit;
+mod unsealer;
 mod userpass;
+mod watcher;
 
 // We use a single binary for integration tests because we want
 // them to run in parallel
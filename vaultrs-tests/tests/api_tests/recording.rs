@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use vaultrs::api::pki::requests::{
+    GenerateCertificateRequest, GenerateRootRequest, SetRoleRequest,
+};
+use vaultrs::api::pki::responses::GenerateCertificateResponse;
+use vaultrs::auth::{approle, aws, oidc};
+use vaultrs::client::recording::{Recorder, Recording, Replayer, REDACTED};
+use vaultrs::client::{Client, VaultClient, VaultClientSettingsBuilder};
+use vaultrs::error::ClientError;
+use vaultrs::fake::FakeVault;
+use vaultrs::kv2;
+use vaultrs::pki::{cert, role};
+use vaultrs::sys::{self, mount};
+
+use crate::common::TestBuilder;
+
+const MOUNT: &str = "pki_recording";
+
+#[tokio::test]
+async fn test_record_and_replay() {
+    TestBuilder::new()
+        .check(|test| async move {
+            let dir = tempfile::tempdir().unwrap();
+            let fixture = dir.path().join("pki.json");
+
+            // Record against the live server, keeping the issued certificates
+            let recorder = Arc::new(Recorder::new(&fixture).redact_data(false));
+            let mut settings = test.client().settings().clone();
+            settings.recording = Some(Recording::Record(recorder.clone()));
+            let client = VaultClient::new(settings).unwrap();
+            let recorded = issue(&client).await;
+            recorder.save().unwrap();
+
+            // Tokens and private keys never make it to the fixture
+            let content = std::fs::read_to_string(&fixture).unwrap();
+            assert!(!content.contains(client.token().expose()));
+            assert!(!content.contains("PRIVATE KEY"));
+            assert!(content.contains(REDACTED));
+
+            // Replay without a server
+            let replayer = Arc::new(Replayer::load(&fixture).unwrap());
+            let client = VaultClient::new(
+                VaultClientSettingsBuilder::default()
+                    .address("http://127.0.0.1:1")
+                    .token("replay")
+                    .recording(Recording::Replay(replayer.clone()))
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+            let replayed = issue(&client).await;
+            assert_eq!(replayed.certificate, recorded.certificate);
+            assert_eq!(replayed.serial_number, recorded.serial_number);
+            assert_eq!(replayed.private_key, REDACTED);
+            assert_eq!(replayer.remaining(), 0);
+
+            // Requests which weren't recorded fail
+            assert!(cert::list(&client, MOUNT).await.is_err());
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn test_secrets_redacted() {
+    let vault = FakeVault::start().await.unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let fixture = dir.path().join("kv2.json");
+
+    let recorder = Arc::new(Recorder::new(&fixture));
+    let mut settings = vault.client().settings().clone();
+    settings.recording = Some(Recording::Record(recorder.clone()));
+    let client = VaultClient::new(settings).unwrap();
+    let secret = HashMap::from([("password", "hunter2"), ("note", "top-secret-note")]);
+    kv2::set(&client, "secret", "app", &secret).await.unwrap();
+    let read: HashMap<String, String> = kv2::read(&client, "secret", "app").await.unwrap();
+    assert_eq!(read["note"], "top-secret-note");
+    recorder.save().unwrap();
+
+    let content = std::fs::read_to_string(&fixture).unwrap();
+    assert!(!content.contains("hunter2"));
+    assert!(!content.contains("top-secret-note"));
+    assert!(!content.contains(client.token().expose()));
+
+    // Redacted responses are still replayed
+    let client = VaultClient::new(
+        VaultClientSettingsBuilder::default()
+            .address("http://127.0.0.1:1")
+            .token("replay")
+            .recording(Recording::Replay(Arc::new(
+                Replayer::load(&fixture).unwrap(),
+            )))
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    kv2::set(&client, "secret", "app", &secret).await.unwrap();
+    let read: HashMap<String, String> = kv2::read(&client, "secret", "app").await.unwrap();
+    assert_eq!(read["note"], REDACTED);
+
+    // A miss is reported right away instead of being retried
+    let start = Instant::now();
+    let res = kv2::read::<HashMap<String, String>>(&client, "secret", "app").await;
+    assert!(matches!(res, Err(ClientError::ReplayMissError { .. })));
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_credentials_redacted() {
+    let vault = FakeVault::start().await.unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let fixture = dir.path().join("login.json");
+
+    let recorder = Arc::new(Recorder::new(&fixture));
+    let mut settings = vault.client().settings().clone();
+    settings.recording = Some(Recording::Record(recorder.clone()));
+    let client = VaultClient::new(settings).unwrap();
+
+    // The fake server doesn't emulate these auth methods, only the recorded
+    // requests matter
+    let _ = aws::iam_login(
+        &client,
+        "aws",
+        "POST",
+        "aHR0cHM6Ly9zdHMuYW1hem9uYXdzLmNvbS8=",
+        r#"{"X-Amz-Security-Token":["sts-session-token"]}"#,
+        "QWN0aW9uPUdldENhbGxlcklkZW50aXR5",
+        Some("dev-role"),
+    )
+    .await;
+    let _ = oidc::login(&client, "jwt", "eyJhbGciOiJSUzI1NiJ9.claims.sig", None).await;
+    let _ = approle::login(&client, "approle", "my-role-id", "my-secret-id").await;
+    let data = HashMap::from([("note", "wrapped-secret-note")]);
+    let _ = sys::wrapping::wrap(&client, &data, None).await;
+    recorder.save().unwrap();
+
+    let content = std::fs::read_to_string(&fixture).unwrap();
+    assert_eq!(recorder.interactions().len(), 4);
+    for leaked in [
+        "aHR0cHM6Ly9zdHMuYW1hem9uYXdzLmNvbS8=",
+        "sts-session-token",
+        "QWN0aW9uPUdldENhbGxlcklkZW50aXR5",
+        "eyJhbGciOiJSUzI1NiJ9",
+        "my-role-id",
+        "my-secret-id",
+        "wrapped-secret-note",
+    ] {
+        assert!(!content.contains(leaked), "{leaked} was recorded");
+    }
+}
+
+async fn issue(client: &impl Client) -> GenerateCertificateResponse {
+    mount::enable(client, MOUNT, "pki", None).await.unwrap();
+    cert::ca::generate(
+        client,
+        MOUNT,
+        "internal",
+        Some(GenerateRootRequest::builder().common_name("Test")),
+    )
+    .await
+    .unwrap();
+    role::set(
+        client,
+        MOUNT,
+        "test",
+        Some(SetRoleRequest::builder().allow_any_name(true)),
+    )
+    .await
+    .unwrap();
+    cert::generate(
+        client,
+        MOUNT,
+        "test",
+        Some(GenerateCertificateRequest::builder().common_name("test.com")),
+    )
+    .await
+    .unwrap()
+}
@@ -49,6 +49,8 @@ pub async fn test_generate_otp(client: &impl Client, endpoint: &SSHEndpoint) ->
     .await
     .unwrap()
     .key
+    .expose()
+    .to_string()
 }
 
 pub async fn test_verify_otp(client: &impl Client, endpoint: &SSHEndpoint, otp: String) {
@@ -1,5 +1,11 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
 use vaultrs::{
-    api::{sys::requests::ListMountsRequest, ResponseWrapper},
+    api::{
+        sys::{requests::ListMountsRequest, responses::StartInitializationResponse},
+        ResponseWrapper, SecretString,
+    },
     client::Client,
     error::ClientError,
     sys,
@@ -15,6 +21,7 @@ async fn test() {
 
             // Test wrapping
             test_wrap(client).await;
+            test_wrap_data(client).await;
 
             // Test health
             test_health(client).await;
@@ -25,10 +32,17 @@ async fn test() {
             // Test status
             test_status(client).await;
 
+            // Test rekey
+            rekey::test_rekey(client).await;
+
+            // Test root generation
+            generate_root::test_generate_root(client).await;
+
             // Test mount
             mount::test_create_mount(client).await;
             mount::test_list_mount(client).await;
             mount::test_get_configuration_of_a_secret_engine(client).await;
+            mount::test_tune_mount(client).await;
             mount::test_delete_mount(client).await;
 
             // Test remount
@@ -37,14 +51,24 @@ async fn test() {
             // Test auth
             auth::test_create_auth(client).await;
             auth::test_list_auth(client).await;
+            auth::test_tune_auth(client).await;
             auth::test_disable_auth(client).await;
 
+            // Test audit
+            audit::test_enable_audit(client).await;
+            audit::test_list_audit(client).await;
+            audit::test_hash_audit(client).await;
+            audit::test_disable_audit(client).await;
+
             // Test policy
             policy::test_set_policy(client).await;
             policy::test_read_policy(client).await;
             policy::test_list_policies(client).await;
             policy::test_delete_policy(client).await;
 
+            // Test capabilities
+            capabilities::test_capabilities(client).await;
+
             // Test tools
             tools::test_random(client).await;
 
@@ -57,9 +81,14 @@ async fn test() {
 #[tokio::test]
 async fn sys_init() {
     TestBuilder::new_prod()
-        .check(|test| async move {
-            let client = test.client();
-            test_start_initialization(client).await;
+        .check(|mut test| async move {
+            let init = test_start_initialization(test.client()).await;
+            test_unseal(test.client(), &init.keys[0]).await;
+            test.client_mut().set_token(init.root_token.expose());
+
+            // Test a full one-share ceremony
+            let key = rekey::test_rekey_ceremony(test.client(), &init.keys[0]).await;
+            generate_root::test_generate_root_ceremony(test.client(), &key).await;
         })
         .await;
 }
@@ -71,7 +100,39 @@ async fn test_wrap(client: &impl Client) {
 
     wrap_resp.unwrap(client).await.unwrap();
 
-    wrap_resp.lookup(client).await.unwrap_err();
+    let err = wrap_resp.lookup(client).await.unwrap_err();
+    assert!(err.is_wrap_invalid());
+
+    let endpoint = ListMountsRequest::builder().build().unwrap();
+    let wrap_resp = endpoint.wrap_with_ttl(client, "5m").await.unwrap();
+    assert_eq!(wrap_resp.info.ttl, 300);
+    wrap_resp.unwrap(client).await.unwrap();
+}
+
+async fn test_wrap_data(client: &impl Client) {
+    let data = HashMap::from([("key", "value")]);
+    let info = sys::wrapping::wrap(client, &data, Some("5m"))
+        .await
+        .unwrap();
+    assert_eq!(info.creation_path, "sys/wrapping/wrap");
+
+    let rewrapped = sys::wrapping::rewrap(client, info.token.expose())
+        .await
+        .unwrap();
+    assert_ne!(rewrapped.token, info.token);
+
+    let unwrapped: HashMap<String, String> =
+        sys::wrapping::unwrap(client, Some(rewrapped.token.expose()))
+            .await
+            .unwrap();
+    assert_eq!(unwrapped["key"], "value");
+
+    // A token created by another endpoint is rejected
+    let endpoint = ListMountsRequest::builder().build().unwrap();
+    let mut wrap_resp = endpoint.wrap(client).await.unwrap();
+    wrap_resp.info = sys::wrapping::wrap(client, &data, None).await.unwrap();
+    let err = wrap_resp.unwrap(client).await.unwrap_err();
+    assert!(matches!(err, ClientError::WrapPathMismatchError { .. }));
 }
 
 async fn test_health(client: &impl Client) {
@@ -82,15 +143,33 @@ async fn test_start_initialization_failure(client: &impl Client) {
     let resp = sys::start_initialization(client, 1, 1, None)
         .await
         .unwrap_err();
+    assert!(resp.is_invalid_request());
     let ClientError::APIError { code, .. } = resp else {
         panic!("must return an error because already initialized")
     };
     assert_eq!(code, 400);
 }
 
-async fn test_start_initialization(client: &impl Client) {
+async fn test_start_initialization(client: &impl Client) -> StartInitializationResponse {
     let resp = sys::start_initialization(client, 1, 1, None).await.unwrap();
     assert_eq!(resp.keys.len(), 1);
+    resp
+}
+
+async fn test_unseal(client: &impl Client, key: &SecretString) {
+    let resp = sys::unseal(client, Some(key.expose().to_string()), None, None)
+        .await
+        .unwrap();
+    assert!(!resp.sealed);
+
+    // The node needs a moment to become active after being unsealed
+    for _ in 0..50 {
+        if matches!(sys::status(client).await.unwrap(), sys::ServerStatus::OK) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("vault did not become active after unsealing");
 }
 
 async fn test_seal(client: &impl Client) {
@@ -104,8 +183,121 @@ async fn test_status(client: &impl Client) {
     ));
 }
 
+mod rekey {
+    use super::Client;
+    use vaultrs::api::sys::requests::StartRekeyRequest;
+    use vaultrs::api::SecretString;
+    use vaultrs::sys::rekey;
+
+    pub async fn test_rekey(client: &impl Client) {
+        assert!(!rekey::status(client).await.unwrap().started);
+
+        let status = rekey::start(
+            client,
+            1,
+            1,
+            Some(StartRekeyRequest::builder().require_verification(true)),
+        )
+        .await
+        .unwrap();
+        assert!(status.started);
+        assert!(status.verification_required);
+        assert_eq!(status.n_shares, 1);
+        assert_eq!(rekey::status(client).await.unwrap().nonce, status.nonce);
+
+        rekey::cancel(client).await.unwrap();
+        assert!(!rekey::status(client).await.unwrap().started);
+    }
+
+    /// Rekeys a one-share Vault and returns the new unseal key.
+    pub async fn test_rekey_ceremony(client: &impl Client, key: &SecretString) -> SecretString {
+        let status = rekey::start(
+            client,
+            1,
+            1,
+            Some(StartRekeyRequest::builder().require_verification(true)),
+        )
+        .await
+        .unwrap();
+
+        let resp = rekey::update(client, key, &status.nonce).await.unwrap();
+        assert!(resp.complete);
+        assert!(resp.verification_required);
+        assert!(!format!("{resp:?}").contains(resp.keys.as_ref().unwrap()[0].expose()));
+        let keys = resp.keys.unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_ne!(&keys[0], key);
+
+        let verification_nonce = resp.verification_nonce.unwrap();
+        assert_eq!(
+            rekey::verification_status(client).await.unwrap().nonce,
+            verification_nonce
+        );
+        let verify = rekey::verify(client, &keys[0], &verification_nonce)
+            .await
+            .unwrap();
+        assert!(verify.complete);
+
+        // Without PGP keys no backup is stored
+        assert!(rekey::read_backup(client).await.is_err());
+
+        keys[0].clone()
+    }
+}
+
+mod generate_root {
+    use super::Client;
+    use vaultrs::api::SecretString;
+    use vaultrs::error::ClientError;
+    use vaultrs::sys::generate_root;
+    use vaultrs::token;
+
+    pub async fn test_generate_root(client: &impl Client) {
+        assert!(!generate_root::status(client).await.unwrap().started);
+
+        let status = generate_root::start(client, None).await.unwrap();
+        assert!(status.started);
+        assert!(!status.complete);
+        assert_eq!(status.otp.unwrap().len() as u64, status.otp_length);
+
+        generate_root::cancel(client).await.unwrap();
+        assert!(!generate_root::status(client).await.unwrap().started);
+    }
+
+    pub async fn test_generate_root_ceremony(client: &impl Client, key: &SecretString) {
+        let status = generate_root::start(client, None).await.unwrap();
+        let otp = status.otp.unwrap();
+
+        let resp = generate_root::update(client, key, &status.nonce)
+            .await
+            .unwrap();
+        assert!(resp.complete);
+        assert_eq!(resp.progress, 1);
+
+        let root = generate_root::decode_token(&resp.encoded_token.unwrap(), &otp).unwrap();
+        let lookup = token::lookup(client, &root).await.unwrap();
+        assert_eq!(lookup.policies, vec!["root"]);
+    }
+
+    #[test]
+    fn test_decode_token() {
+        let token = generate_root::decode_token(
+            "USJCax5XHncxYTAjOBdYABIudS85UA0feDY",
+            "9T1EH0ZDDXhRoVkhAx1xmgFeLT",
+        )
+        .unwrap();
+        assert_eq!(token, "hvs.VgD3u9XqWA3hSVDWT7Kz4b");
+
+        assert!(matches!(
+            generate_root::decode_token("USJCax5XHncxYTAjOBdYABIudS85UA0feDY", "short"),
+            Err(ClientError::RootTokenDecodeError)
+        ));
+    }
+}
+
 mod mount {
     use super::Client;
+    use vaultrs::api::sys::requests::{MountConfigBuilder, TuneMountRequest};
     use vaultrs::sys::mount;
 
     pub async fn test_create_mount(client: &impl Client) {
@@ -123,6 +315,34 @@ mod mount {
             .unwrap();
     }
 
+    pub async fn test_tune_mount(client: &impl Client) {
+        mount::tune(
+            client,
+            "pki_temp",
+            Some(
+                TuneMountRequest::builder()
+                    .config(
+                        MountConfigBuilder::default()
+                            .max_lease_ttl("87600h")
+                            .audit_non_hmac_request_keys(vec!["common_name".to_string()])
+                            .build()
+                            .unwrap(),
+                    )
+                    .description("temporary pki"),
+            ),
+        )
+        .await
+        .unwrap();
+
+        let resp = mount::read_tune(client, "pki_temp").await.unwrap();
+        assert_eq!(resp.max_lease_ttl, 87600 * 60 * 60);
+        assert_eq!(resp.description.as_deref(), Some("temporary pki"));
+        assert_eq!(
+            resp.audit_non_hmac_request_keys,
+            Some(vec!["common_name".to_string()])
+        );
+    }
+
     pub async fn test_delete_mount(client: &impl Client) {
         mount::disable(client, "pki_temp").await.unwrap();
         mount::get_configuration_of_a_secret_engine(client, "pki_temp")
@@ -182,6 +402,7 @@ mod remount {
 
 mod auth {
     use super::Client;
+    use vaultrs::api::sys::requests::{MountConfigBuilder, TuneAuthRequest};
     use vaultrs::sys::auth;
 
     pub async fn test_create_auth(client: &impl Client) {
@@ -194,11 +415,91 @@ mod auth {
         auth::list(client).await.unwrap();
     }
 
+    pub async fn test_tune_auth(client: &impl Client) {
+        auth::tune(
+            client,
+            "oidc_temp",
+            Some(
+                TuneAuthRequest::builder()
+                    .config(
+                        MountConfigBuilder::default()
+                            .default_lease_ttl("1h")
+                            .listing_visibility("unauth")
+                            .build()
+                            .unwrap(),
+                    )
+                    .token_type("batch"),
+            ),
+        )
+        .await
+        .unwrap();
+
+        let resp = auth::read_tune(client, "oidc_temp").await.unwrap();
+        assert_eq!(resp.default_lease_ttl, 60 * 60);
+        assert_eq!(resp.listing_visibility.as_deref(), Some("unauth"));
+        assert_eq!(resp.token_type.as_deref(), Some("batch"));
+    }
+
     pub async fn test_disable_auth(client: &impl Client) {
         auth::disable(client, "oidc_temp").await.unwrap();
     }
 }
 
+mod audit {
+    use super::Client;
+    use vaultrs::api::sys::requests::{
+        AuditDevice, EnableAuditDeviceRequest, FileAuditDeviceOptionsBuilder,
+    };
+    use vaultrs::sys::audit;
+
+    pub async fn test_enable_audit(client: &impl Client) {
+        audit::enable(
+            client,
+            "file_temp",
+            AuditDevice::File(
+                FileAuditDeviceOptionsBuilder::default()
+                    .file_path("stdout")
+                    .hmac_accessor(false)
+                    .build()
+                    .unwrap(),
+            ),
+            Some(EnableAuditDeviceRequest::builder().description("Temporary audit device")),
+        )
+        .await
+        .unwrap();
+    }
+
+    pub async fn test_list_audit(client: &impl Client) {
+        let devices = audit::list(client).await.unwrap();
+        let device = &devices["file_temp/"];
+        assert_eq!(device.device_type, "file");
+        assert_eq!(device.description, "Temporary audit device");
+        assert_eq!(device.options.as_ref().unwrap()["file_path"], "stdout");
+    }
+
+    pub async fn test_hash_audit(client: &impl Client) {
+        let hash = audit::hash(client, "file_temp", "supersecret")
+            .await
+            .unwrap();
+        assert!(hash.hash.starts_with("hmac-sha256:"));
+        assert_eq!(
+            audit::hash(client, "file_temp", "supersecret")
+                .await
+                .unwrap()
+                .hash,
+            hash.hash
+        );
+    }
+
+    pub async fn test_disable_audit(client: &impl Client) {
+        audit::disable(client, "file_temp").await.unwrap();
+        assert!(!audit::list(client)
+            .await
+            .unwrap()
+            .contains_key("file_temp/"));
+    }
+}
+
 mod policy {
     use super::Client;
     use vaultrs::sys::policy;
@@ -225,6 +526,69 @@ mod policy {
     }
 }
 
+mod capabilities {
+    use super::Client;
+    use vaultrs::api::sys::responses::CapabilitiesResponse;
+    use vaultrs::api::sys::Capability;
+    use vaultrs::api::token::requests::CreateTokenRequest;
+    use vaultrs::sys::{capabilities, policy};
+    use vaultrs::token;
+
+    #[test]
+    fn test_unknown_capability() {
+        let resp: CapabilitiesResponse = serde_json::from_str(
+            r#"{"capabilities": ["read", "subscribe", "frobnicate"], "secret/data/app": ["read"]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            resp.capabilities.unwrap(),
+            vec![Capability::Read, Capability::Subscribe, Capability::Unknown]
+        );
+        assert_eq!(resp.paths["secret/data/app"], vec![Capability::Read]);
+    }
+
+    pub async fn test_capabilities(client: &impl Client) {
+        let policy = r#"
+            path "secret/data/allowed" {
+                capabilities = ["read", "list"]
+            }
+            path "secret/data/denied" {
+                capabilities = ["deny"]
+            }"#;
+        policy::set(client, "capabilities", policy).await.unwrap();
+        let auth = token::new(
+            client,
+            Some(CreateTokenRequest::builder().policies(vec!["capabilities".to_string()])),
+        )
+        .await
+        .unwrap();
+        let paths = ["secret/data/allowed", "secret/data/denied"];
+
+        let caps = capabilities::token(client, &auth.client_token, &paths)
+            .await
+            .unwrap();
+        assert_eq!(
+            caps["secret/data/allowed"],
+            vec![Capability::List, Capability::Read]
+        );
+        assert_eq!(caps["secret/data/denied"], vec![Capability::Deny]);
+
+        let caps = capabilities::accessor(client, &auth.accessor, &paths)
+            .await
+            .unwrap();
+        assert_eq!(caps["secret/data/denied"], vec![Capability::Deny]);
+
+        let caps = capabilities::token_self(client, &paths).await.unwrap();
+        assert_eq!(caps["secret/data/allowed"], vec![Capability::Root]);
+        assert_eq!(
+            client.capabilities("sys/mounts").await.unwrap(),
+            vec![Capability::Root]
+        );
+
+        policy::delete(client, "capabilities").await.unwrap();
+    }
+}
+
 mod tools {
     use super::Client;
     use vaultrs::{api::sys::requests::RandomRequestBuilder, sys::tools};
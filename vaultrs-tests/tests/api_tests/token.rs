@@ -159,6 +159,6 @@ async fn setup(client: &impl Client) -> Result<Token, ClientError> {
     .await?;
     Ok(Token {
         accessor: resp.accessor,
-        token: resp.client_token,
+        token: resp.client_token.expose().to_string(),
     })
 }
@@ -0,0 +1,142 @@
+use std::future::Ready;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use reqwest::Url;
+use vaultrs::api::SecretString;
+use vaultrs::client::retry::RetryPolicy;
+use vaultrs::client::VaultClientSettingsBuilder;
+use vaultrs::error::ClientError;
+use vaultrs::fake::FakeVault;
+use vaultrs::sys::{self, ServerStatus};
+use vaultrs::unsealer::{KeyShares, Unsealer};
+
+#[tokio::test]
+async fn test_unseal_nodes() {
+    let vault = FakeVault::start().await.unwrap();
+    let settings = VaultClientSettingsBuilder::default()
+        .addresses(["http://127.0.0.1:1", vault.address().as_str()])
+        .build()
+        .unwrap();
+
+    // Unsealed nodes don't request any key share
+    let shares = |_: &Url, _: usize| -> Ready<Result<Option<SecretString>, ClientError>> {
+        panic!("no key share should be requested")
+    };
+    let reports = Unsealer::new(settings, shares)
+        .reset(true)
+        .on_progress(|_| panic!("no progress should be reported"))
+        .run()
+        .await;
+
+    assert_eq!(reports.len(), 2);
+    assert_eq!(reports[0].address.as_str(), "http://127.0.0.1:1/");
+    assert!(reports[0].status.is_err());
+    assert!(matches!(reports[1].status, Ok(ServerStatus::OK)));
+    assert!(reports[1].progress.is_none());
+}
+
+#[tokio::test]
+async fn test_unseal_sealed_node() {
+    let vault = FakeVault::start().await.unwrap();
+    let settings = VaultClientSettingsBuilder::default()
+        .address(vault.address())
+        .retry(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    // Shares are prompted for one at a time until the node is unsealed
+    vault.seal(["share-1", "share-2", "share-3"], 2);
+    let prompted = Arc::new(Mutex::new(Vec::new()));
+    let shares = |address: &Url, index: usize| {
+        prompted.lock().unwrap().push(index);
+        let address = address.clone();
+        async move {
+            // Answered off the runtime like a terminal prompt
+            let share = tokio::task::spawn_blocking(move || format!("share-{}", index + 1))
+                .await
+                .unwrap();
+            assert_eq!(address.host_str(), Some("127.0.0.1"));
+            Ok(Some(SecretString::from(share)))
+        }
+    };
+    let progress = Arc::new(Mutex::new(Vec::new()));
+    let reported = progress.clone();
+    let reports = Unsealer::new(settings.clone(), shares)
+        .on_progress(move |p| {
+            reported
+                .lock()
+                .unwrap()
+                .push((p.sealed, p.progress, p.threshold))
+        })
+        .run()
+        .await;
+    assert!(matches!(reports[0].status, Ok(ServerStatus::OK)));
+    assert_eq!(*prompted.lock().unwrap(), vec![0, 1]);
+    assert_eq!(*progress.lock().unwrap(), vec![(true, 1, 2), (false, 0, 2)]);
+    let last = reports[0].progress.as_ref().unwrap();
+    assert!(!last.sealed);
+
+    // The progress of a previous attempt is discarded with reset, otherwise
+    // the same share is rejected
+    vault.seal(["share-1", "share-2"], 2);
+    let client = vault.client();
+    sys::unseal(&client, Some("share-1".to_string()), None, None)
+        .await
+        .unwrap();
+    let reports = Unsealer::new(settings.clone(), KeyShares::new(["share-1", "share-2"]))
+        .run()
+        .await;
+    assert!(reports[0].status.as_ref().unwrap_err().is_invalid_request());
+    let reports = Unsealer::new(settings.clone(), KeyShares::new(["share-1", "share-2"]))
+        .reset(true)
+        .run()
+        .await;
+    assert!(matches!(reports[0].status, Ok(ServerStatus::OK)));
+    assert_eq!(reports[0].progress.as_ref().unwrap().progress, 0);
+
+    // Running out of shares leaves the node sealed
+    vault.seal(["share-1", "share-2"], 2);
+    let reports = Unsealer::new(settings.clone(), KeyShares::new(["share-1"]))
+        .run()
+        .await;
+    assert!(matches!(reports[0].status, Ok(ServerStatus::SEALED)));
+    let last = reports[0].progress.as_ref().unwrap();
+    assert!(last.sealed);
+    assert_eq!(last.progress, 1);
+
+    // A pending seal migration requires the migrate option
+    vault.seal_for_migration(["share-1"], 1);
+    let reports = Unsealer::new(settings.clone(), KeyShares::new(["share-1"]))
+        .reset(true)
+        .run()
+        .await;
+    assert!(reports[0].status.as_ref().unwrap_err().is_invalid_request());
+    let reports = Unsealer::new(settings, KeyShares::new(["share-1"]))
+        .migrate(true)
+        .run()
+        .await;
+    assert!(matches!(reports[0].status, Ok(ServerStatus::OK)));
+}
+
+#[test]
+fn test_key_shares() {
+    let mut first = tempfile::NamedTempFile::new().unwrap();
+    writeln!(first, "share-1\n\n  share-2  ").unwrap();
+    let mut second = tempfile::NamedTempFile::new().unwrap();
+    writeln!(second, "share-3").unwrap();
+
+    let shares = KeyShares::from_files([first.path(), second.path()]).unwrap();
+    assert_eq!(shares.len(), 3);
+
+    assert!(matches!(
+        KeyShares::from_files(["/does/not/exist"]),
+        Err(ClientError::FileReadError { .. })
+    ));
+
+    std::env::set_var("VAULTRS_TEST_UNSEAL_KEY_1", "share-1");
+    std::env::set_var("VAULTRS_TEST_UNSEAL_KEY_2", "share-2");
+    std::env::set_var("VAULTRS_TEST_UNSEAL_KEY_4", "share-4");
+    assert_eq!(KeyShares::from_env("VAULTRS_TEST_UNSEAL_KEY").len(), 2);
+    assert!(KeyShares::from_env("VAULTRS_TEST_MISSING_KEY").is_empty());
+}
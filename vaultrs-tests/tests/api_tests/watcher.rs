@@ -0,0 +1,199 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+use vaultrs::api::auth::userpass::requests::CreateUserRequest;
+use vaultrs::api::token::requests::CreateTokenRequest;
+use vaultrs::api::AuthInfo;
+use vaultrs::auth::userpass;
+use vaultrs::client::{Client, VaultClient, VaultClientSettingsBuilder};
+use vaultrs::error::ClientError;
+use vaultrs::fake::FakeVault;
+use vaultrs::sys::auth;
+use vaultrs::token;
+use vaultrs_login::engines::userpass::UserpassLogin;
+use vaultrs_login::watcher::{LifetimeWatcher, WatcherConfig, WatcherEvent};
+use vaultrs_login::{LoginClient, LoginMethod};
+
+use crate::common::TestBuilder;
+
+#[tokio::test]
+async fn test() {
+    TestBuilder::new()
+        .check(|test| async move {
+            let client = test.client();
+            auth::enable(client, "userpass_watcher", "userpass", None)
+                .await
+                .unwrap();
+            userpass::user::set(
+                client,
+                "userpass_watcher",
+                "test",
+                "test",
+                Some(
+                    &mut CreateUserRequest::builder()
+                        .token_ttl("3s")
+                        .token_explicit_max_ttl("6s"),
+                ),
+            )
+            .await
+            .unwrap();
+
+            test_renew_and_login(client).await;
+        })
+        .await;
+}
+
+async fn test_renew_and_login(client: &VaultClient) {
+    let mut watched = VaultClient::new(client.settings().clone()).unwrap();
+    watched
+        .login("userpass_watcher", &UserpassLogin::new("test", "test"))
+        .await
+        .unwrap();
+    let watched = Arc::new(RwLock::new(watched));
+
+    let mut handle = LifetimeWatcher::new(watched.clone())
+        .with_config(WatcherConfig {
+            retry_delay: Duration::from_millis(500),
+            ..Default::default()
+        })
+        .with_login("userpass_watcher", UserpassLogin::new("test", "test"))
+        .start();
+
+    let (mut renewed, mut capped) = (false, false);
+    let logged_in = tokio::time::timeout(Duration::from_secs(30), async {
+        while let Some(event) = handle.next_event().await {
+            match event {
+                WatcherEvent::Renewed(_) => renewed = true,
+                WatcherEvent::MaxTtlReached => capped = true,
+                WatcherEvent::LoggedIn(_) => return true,
+                WatcherEvent::Error(e) => panic!("watcher failed: {e}"),
+                WatcherEvent::Expired => return false,
+            }
+        }
+        false
+    })
+    .await
+    .unwrap();
+
+    assert!(renewed);
+    assert!(capped);
+    assert!(logged_in);
+    watched.read().await.lookup().await.unwrap();
+    handle.stop().await;
+}
+
+#[tokio::test]
+async fn test_lookup_retried() {
+    // Nothing listens on this port, so every lookup fails
+    let client = VaultClient::new(
+        VaultClientSettingsBuilder::default()
+            .address("http://127.0.0.1:1")
+            .token("test")
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    let mut handle = LifetimeWatcher::new(Arc::new(RwLock::new(client)))
+        .with_config(WatcherConfig {
+            retry_delay: Duration::from_millis(50),
+            ..Default::default()
+        })
+        .start();
+
+    for _ in 0..2 {
+        let event = tokio::time::timeout(Duration::from_secs(10), handle.next_event())
+            .await
+            .unwrap();
+        assert!(matches!(event, Some(WatcherEvent::Error(_))));
+    }
+    handle.stop().await;
+}
+
+#[tokio::test]
+async fn test_renew_shorter_increment() {
+    let vault = FakeVault::start().await.unwrap();
+    let mut client = vault.client();
+    let auth = token::new(
+        &client,
+        Some(CreateTokenRequest::builder().ttl("1h").renewable(true)),
+    )
+    .await
+    .unwrap();
+    client.set_token(auth.client_token.expose());
+
+    // Renewing for less than the creation TTL doesn't mean the max TTL was
+    // reached
+    let mut handle = LifetimeWatcher::new(Arc::new(RwLock::new(client)))
+        .with_config(WatcherConfig {
+            renew_fraction: 0.0,
+            increment: Some("30m".to_string()),
+            ..Default::default()
+        })
+        .start();
+    for _ in 0..3 {
+        let event = tokio::time::timeout(Duration::from_secs(10), handle.next_event())
+            .await
+            .unwrap();
+        assert!(matches!(event, Some(WatcherEvent::Renewed(_))), "{event:?}");
+    }
+    handle.stop().await;
+}
+
+/// Logs in with a token which doesn't expire.
+struct NonExpiringLogin;
+
+#[async_trait]
+impl LoginMethod for NonExpiringLogin {
+    async fn login(&self, _: &impl Client, _: &str) -> Result<AuthInfo, ClientError> {
+        Ok(serde_json::from_value(serde_json::json!({
+            "client_token": "non-expiring",
+            "accessor": "",
+            "policies": [],
+            "token_policies": [],
+            "metadata": null,
+            "lease_duration": 0,
+            "renewable": false,
+            "entity_id": "",
+            "token_type": "service",
+            "orphan": true,
+        }))
+        .unwrap())
+    }
+}
+
+#[tokio::test]
+async fn test_login_non_expiring() {
+    let client = VaultClient::new(
+        VaultClientSettingsBuilder::default()
+            .address("http://127.0.0.1:1")
+            .token("test")
+            .build()
+            .unwrap(),
+    )
+    .unwrap();
+    let client = Arc::new(RwLock::new(client));
+    let mut handle = LifetimeWatcher::new(client.clone())
+        .with_config(WatcherConfig {
+            retry_delay: Duration::from_millis(50),
+            ..Default::default()
+        })
+        .with_login("test", NonExpiringLogin)
+        .start();
+
+    // The watcher stops instead of renewing and logging in again in a loop
+    let events = tokio::time::timeout(Duration::from_secs(10), async {
+        let mut events = Vec::new();
+        while let Some(event) = handle.next_event().await {
+            events.push(event);
+        }
+        events
+    })
+    .await
+    .unwrap();
+    assert!(matches!(
+        events.as_slice(),
+        [WatcherEvent::Error(_), WatcherEvent::LoggedIn(_)]
+    ));
+    assert_eq!(client.read().await.token().expose(), "non-expiring");
+}
@@ -5,6 +5,7 @@ pub mod database;
 pub mod identity;
 pub mod kv1;
 pub mod kv2;
+pub mod logical;
 pub mod pki;
 pub mod ssh;
 pub mod sys;
@@ -12,13 +13,21 @@ pub mod token;
 pub mod transit;
 
 use std::collections::HashMap;
+use std::fmt;
+use std::ops::Deref;
 use std::str::FromStr;
+use std::sync::{Arc, RwLock};
 
 use async_trait::async_trait;
 use rustify::endpoint::{Endpoint, MiddleWare};
 use rustify::errors::ClientError as RestClientError;
-use serde::{de::DeserializeOwned, Deserialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use zeroize::Zeroize;
 
+use crate::client::consistency::Consistency;
+use crate::client::failover::FailoverClient;
+use crate::client::middleware::MiddlewareChain;
+use crate::client::recording::RecordingClient;
 use crate::sys::wrapping;
 use crate::{client::Client, error::ClientError};
 
@@ -51,7 +60,7 @@ impl<T: DeserializeOwned + Send + Sync> rustify::endpoint::Wrapper for EndpointR
 /// The information stored in the optional `wrap_info` field of API responses
 #[derive(Deserialize, Debug)]
 pub struct WrapInfo {
-    pub token: String,
+    pub token: SecretString,
     pub accessor: String,
     pub ttl: u64,
     pub creation_time: String,
@@ -61,7 +70,7 @@ pub struct WrapInfo {
 /// The information stored in the optional `auth` field of API responses
 #[derive(Deserialize, Debug)]
 pub struct AuthInfo {
-    pub client_token: String,
+    pub client_token: SecretString,
     pub accessor: String,
     pub policies: Vec<String>,
     pub token_policies: Vec<String>,
@@ -73,6 +82,22 @@ pub struct AuthInfo {
     pub orphan: bool,
 }
 
+/// A secret returned by the Vault API together with the details of the lease
+/// it was issued with.
+///
+/// Dynamic secrets (database credentials, AWS credentials, certificates, etc.)
+/// are attached to a lease which can be renewed or revoked using its
+/// `lease_id`. See [exec_with_lease].
+#[derive(Debug)]
+pub struct Secret<T> {
+    pub data: T,
+    pub lease_id: String,
+    pub lease_duration: u64,
+    pub renewable: bool,
+    pub request_id: String,
+    pub warnings: Vec<String>,
+}
+
 /// Represents an API response that has been wrapped by a unique token.
 ///
 /// See [response wrapping][<https://developer.hashicorp.com/vault/docs/concepts/response-wrapping>] for details on how this works. This struct stores
@@ -83,29 +108,49 @@ pub struct AuthInfo {
 pub struct WrappedResponse<E: Endpoint> {
     pub info: WrapInfo,
     pub endpoint: rustify::endpoint::EndpointResult<E::Response>,
+    path: String,
+    namespace: Option<String>,
 }
 
 impl<E: Endpoint> WrappedResponse<E> {
+    /// Returns the path of the endpoint which was wrapped.
+    pub fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
     /// Retrieves information about this wrapped response
     pub async fn lookup(
         &self,
         client: &impl Client,
     ) -> Result<WrappingLookupResponse, ClientError> {
         debug!("Looking up wrapped response information");
-        wrapping::lookup(client, self.info.token.as_str())
+        wrapping::lookup(client, self.info.token.expose())
             .await
             .map_err(|e| match &e {
-                ClientError::APIError {
-                    code: 400,
-                    errors: _,
-                } => ClientError::WrapInvalidError,
+                ClientError::APIError { code: 400, .. } => ClientError::WrapInvalidError,
                 _ => e,
             })
     }
 
-    /// Unwraps this response, returning the original response
+    /// Unwraps this response, returning the original response.
+    ///
+    /// The token is looked up before being unwrapped in order to verify that
+    /// it was created by the endpoint which was wrapped. A
+    /// [ClientError::WrapPathMismatchError] is returned if it wasn't, which
+    /// indicates the token was tampered with.
     pub async fn unwrap(&self, client: &impl Client) -> Result<E::Response, ClientError> {
-        wrapping::unwrap(client, Some(self.info.token.as_str())).await
+        let info = self.lookup(client).await?;
+        if !matches_path(
+            info.creation_path.as_str(),
+            self.namespace.as_deref(),
+            self.path.as_str(),
+        ) {
+            return Err(ClientError::WrapPathMismatchError {
+                expected: self.path.clone(),
+                actual: info.creation_path,
+            });
+        }
+        wrapping::unwrap(client, Some(self.info.token.expose())).await
     }
 }
 
@@ -115,6 +160,16 @@ pub trait ResponseWrapper: Endpoint {
     async fn wrap(self, client: &impl Client) -> Result<WrappedResponse<Self>, ClientError> {
         wrap(client, self).await
     }
+
+    /// Executes the endpoint, returning a wrapped response which is valid for
+    /// the given TTL instead of the `wrap_ttl` configured on the client.
+    async fn wrap_with_ttl(
+        self,
+        client: &impl Client,
+        ttl: &str,
+    ) -> Result<WrappedResponse<Self>, ClientError> {
+        wrap_with_ttl(client, self, ttl).await
+    }
 }
 
 impl<E: Endpoint> ResponseWrapper for E {}
@@ -134,12 +189,17 @@ pub struct EndpointError {
 /// information to all requests and adding a Vault token to the header of all
 /// requests. This is automatically passed by the API functions when an endpoint
 /// is executed.
+///
+/// The token is a [SharedToken] which is read every time a request is sent,
+/// so it can be swapped while requests are in flight.
 #[derive(Debug, Clone)]
 pub struct EndpointMiddleware {
-    pub token: String,
+    pub token: SharedToken,
     pub version: String,
     pub wrap: Option<String>,
     pub namespace: Option<String>,
+    pub chain: MiddlewareChain,
+    pub consistency: Option<Consistency>,
 }
 impl MiddleWare for EndpointMiddleware {
     fn request<E: Endpoint>(
@@ -166,13 +226,22 @@ impl MiddleWare for EndpointMiddleware {
             http::HeaderValue::from_str("true").unwrap(),
         );
 
+        // Vault only accepts PATCH requests sent as a JSON merge patch
+        if req.method() == http::Method::PATCH {
+            trace!("Middleware: setting merge patch content type");
+            req.headers_mut().insert(
+                http::header::CONTENT_TYPE,
+                http::HeaderValue::from_static("application/merge-patch+json"),
+            );
+        }
+
         // Add Vault token to all requests
-        if !self.token.is_empty() {
+        let token = self.token.get();
+        if !token.is_empty() {
             trace!("Middleware: adding token to header");
-            req.headers_mut().append(
-                "X-Vault-Token",
-                http::HeaderValue::from_str(self.token.as_str()).unwrap(),
-            );
+            let mut value = http::HeaderValue::from_str(token.expose()).unwrap();
+            value.set_sensitive(true);
+            req.headers_mut().append("X-Vault-Token", value);
         }
 
         // Optionally wrap response
@@ -193,15 +262,146 @@ impl MiddleWare for EndpointMiddleware {
             );
         }
 
-        Ok(())
+        // Require the storage states seen so far
+        if let Some(consistency) = &self.consistency {
+            trace!("Middleware: adding storage states");
+            consistency.request(req);
+        }
+
+        // Run the user supplied middlewares last so they see the final request
+        self.chain.request(req).map_err(middleware_err)
     }
 
     fn response<E: Endpoint>(
         &self,
         _: &E,
-        _: &mut http::Response<Vec<u8>>,
+        resp: &mut http::Response<Vec<u8>>,
     ) -> Result<(), rustify::errors::ClientError> {
-        Ok(())
+        if let Some(consistency) = &self.consistency {
+            consistency.response(resp);
+        }
+        self.chain.response(resp).map_err(middleware_err)
+    }
+}
+
+/// Passes an error returned by a [Middleware][crate::client::middleware::Middleware]
+/// through rustify, it's recovered by [parse_err].
+fn middleware_err(e: ClientError) -> RestClientError {
+    RestClientError::GenericError { source: e.into() }
+}
+
+/// A token which can be swapped atomically and is shared between all of its
+/// clones.
+///
+/// Clients keep their token in a [SharedToken] so that it can be replaced
+/// through a shared reference, e.g. after logging in again, while other tasks
+/// keep sending requests with the same client.
+#[derive(Clone, Debug, Default)]
+pub struct SharedToken(Arc<RwLock<SecretString>>);
+
+impl SharedToken {
+    pub fn new(token: &str) -> Self {
+        SharedToken(Arc::new(RwLock::new(token.into())))
+    }
+
+    /// Returns the current token
+    pub fn get(&self) -> SecretString {
+        self.0.read().unwrap().clone()
+    }
+
+    /// Replaces the token, affecting every clone of this [SharedToken]
+    pub fn set(&self, token: &str) {
+        *self.0.write().unwrap() = token.into();
+    }
+}
+
+/// A string holding a secret, like a token or a password.
+///
+/// Its [Debug] implementation never prints the secret, so that structs
+/// holding one can be logged safely, and its memory is zeroed when dropped.
+/// The secret is read through [SecretString::expose] or by dereferencing it
+/// to a `&str`.
+#[derive(Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Wraps the given secret.
+    pub fn new(secret: impl Into<String>) -> Self {
+        SecretString(secret.into())
+    }
+
+    /// Returns the secret.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"[REDACTED]\"")
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Deref for SecretString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        SecretString(secret)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(secret: &str) -> Self {
+        SecretString(secret.to_string())
+    }
+}
+
+impl From<&String> for SecretString {
+    fn from(secret: &String) -> Self {
+        SecretString(secret.clone())
+    }
+}
+
+impl PartialEq<str> for SecretString {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for SecretString {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<String> for SecretString {
+    fn eq(&self, other: &String) -> bool {
+        &self.0 == other
+    }
+}
+
+impl PartialEq<SecretString> for String {
+    fn eq(&self, other: &SecretString) -> bool {
+        self == &other.0
+    }
+}
+
+impl PartialEq<SecretString> for &str {
+    fn eq(&self, other: &SecretString) -> bool {
+        *self == other.0
     }
 }
 
@@ -215,9 +415,7 @@ where
     E: Endpoint,
 {
     trace!("start request");
-    endpoint
-        .with_middleware(client.middle())
-        .exec(client.http())
+    execute(client, endpoint, client.middle())
         .await
         .map_err(parse_err)
         .map(|_| ())
@@ -233,13 +431,11 @@ where
     E: Endpoint,
 {
     trace!("start request");
-    endpoint
-        .with_middleware(client.middle())
-        .exec(client.http())
+    execute(client, endpoint, client.middle())
         .await
-        .map_err(ClientError::from)?
+        .map_err(parse_err)?
         .wrap::<EndpointResult<_>>()
-        .map_err(parse_err)
+        .map_err(ClientError::from)
         .map(strip)
         .map(|_| ())
 }
@@ -257,9 +453,7 @@ where
     E: Endpoint,
 {
     trace!("start request");
-    endpoint
-        .with_middleware(client.middle())
-        .exec(client.http())
+    execute(client, endpoint, client.middle())
         .await
         .map_err(parse_err)?
         .parse()
@@ -291,9 +485,7 @@ where
     E: Endpoint,
 {
     trace!("start request");
-    endpoint
-        .with_middleware(client.middle())
-        .exec(client.http())
+    execute(client, endpoint, client.middle())
         .await
         .map_err(parse_err)?
         .wrap::<EndpointResult<_>>()
@@ -302,12 +494,84 @@ where
         .ok_or(ClientError::ResponseDataEmptyError)
 }
 
+/// Executes an [Endpoint] and returns the result along with its lease.
+///
+/// This behaves like [exec_with_result] but instead of discarding the
+/// enclosing [EndpointResult], the lease information and warnings it contains
+/// are returned in a [Secret].
+#[instrument(name = "request", skip_all, fields(method = ?endpoint.method(), path = %endpoint.path()), err)]
+pub async fn exec_with_lease<E>(
+    client: &impl Client,
+    endpoint: E,
+) -> Result<Secret<E::Response>, ClientError>
+where
+    E: Endpoint,
+{
+    trace!("start request");
+    execute(client, endpoint, client.middle())
+        .await
+        .map_err(parse_err)?
+        .wrap::<EndpointResult<_>>()
+        .map_err(ClientError::from)
+        .map(strip_lease)?
+}
+
+/// Executes an [Endpoint] and returns the complete [EndpointResult] it
+/// responded with, parsing its `data` field as the given type.
+///
+/// Unlike [exec_with_result], the response is not required to contain any
+/// data and [None] is returned if the server responded with an empty body.
+#[instrument(name = "request", skip_all, fields(method = ?endpoint.method(), path = %endpoint.path()), err)]
+pub async fn exec_with_envelope<E, T>(
+    client: &impl Client,
+    endpoint: E,
+) -> Result<Option<EndpointResult<T>>, ClientError>
+where
+    E: Endpoint,
+    T: DeserializeOwned,
+{
+    trace!("start request");
+    let resp = execute(client, endpoint, client.middle())
+        .await
+        .map_err(parse_err)?;
+    let body = resp.response.body();
+    if body.is_empty() {
+        return Ok(None);
+    }
+    let result: EndpointResult<T> =
+        serde_json::from_slice(body).map_err(|e| ClientError::JsonParseError { source: e })?;
+    if let Some(w) = &result.warnings {
+        if !w.is_empty() {
+            warn!("Detected warnings in API response: {:#?}", w);
+        }
+    }
+    Ok(Some(result))
+}
+
 /// Executes the given endpoint but requests that the Vault server to return a
 /// token wrapped response.
 ///
 /// The token is stored in a [WrappedResponse] and the original response can
-/// be fetched using the `unwrap` method provided by the struct.
+/// be fetched using the `unwrap` method provided by the struct. The token is
+/// valid for the `wrap_ttl` configured on the client, use [wrap_with_ttl] to
+/// override it.
 pub async fn wrap<E>(client: &impl Client, endpoint: E) -> Result<WrappedResponse<E>, ClientError>
+where
+    E: Endpoint,
+{
+    let ttl = client.settings().wrap_ttl.clone();
+    wrap_with_ttl(client, endpoint, ttl.as_str()).await
+}
+
+/// Executes the given endpoint but requests that the Vault server to return a
+/// token wrapped response which is valid for the given TTL.
+///
+/// See [wrap]
+pub async fn wrap_with_ttl<E>(
+    client: &impl Client,
+    endpoint: E,
+    ttl: &str,
+) -> Result<WrappedResponse<E>, ClientError>
 where
     E: Endpoint,
 {
@@ -315,13 +579,10 @@ where
         "Executing {} and returning a wrapped response",
         endpoint.path()
     );
+    let path = endpoint.path();
     let mut m = client.middle().clone();
-    m.wrap = Some("10m".to_string());
-    let resp = endpoint
-        .with_middleware(&m)
-        .exec(client.http())
-        .await
-        .map_err(parse_err)?;
+    m.wrap = Some(ttl.to_string());
+    let resp = execute(client, endpoint, &m).await.map_err(parse_err)?;
     let info = resp
         .wrap::<EndpointResult<_>>()
         .map_err(ClientError::from)
@@ -329,9 +590,29 @@ where
     Ok(WrappedResponse {
         info,
         endpoint: resp,
+        path,
+        namespace: m.namespace,
     })
 }
 
+/// Executes an [Endpoint] which is expected to return a token wrapped
+/// response without being asked to, returning its [WrapInfo].
+pub async fn exec_with_wrap_info<E>(
+    client: &impl Client,
+    endpoint: E,
+) -> Result<WrapInfo, ClientError>
+where
+    E: Endpoint,
+{
+    trace!("Executing {} and returning its wrap info", endpoint.path());
+    execute(client, endpoint, client.middle())
+        .await
+        .map_err(parse_err)?
+        .wrap::<EndpointResult<_>>()
+        .map_err(ClientError::from)
+        .map(strip_wrap)?
+}
+
 pub async fn auth<E>(client: &impl Client, endpoint: E) -> Result<AuthInfo, ClientError>
 where
     E: Endpoint<Response = ()>,
@@ -340,9 +621,7 @@ where
         "Executing {} and returning authentication info",
         endpoint.path()
     );
-    let r: EndpointResult<()> = endpoint
-        .with_middleware(client.middle())
-        .exec(client.http())
+    let r: EndpointResult<()> = execute(client, endpoint, client.middle())
         .await
         .map_err(parse_err)?
         .wrap::<EndpointResult<_>>()
@@ -350,6 +629,49 @@ where
     r.auth.ok_or(ClientError::ResponseEmptyError)
 }
 
+/// Executes an [Endpoint] using the given [MiddleWare], retrying it according
+/// to the [RetryPolicy][crate::client::retry::RetryPolicy] configured on the
+/// client and waiting for its [RateLimit][crate::client::rate_limit::RateLimit].
+/// Requests are recorded or replayed according to its
+/// [Recording][crate::client::recording::Recording] and sent to the nodes of
+/// its [Failover][crate::client::failover::Failover].
+async fn execute<E, M>(
+    client: &impl Client,
+    endpoint: E,
+    middle: &M,
+) -> Result<rustify::endpoint::EndpointResult<E::Response>, RestClientError>
+where
+    E: Endpoint,
+    M: MiddleWare,
+{
+    let settings = client.settings();
+    let policy = &settings.retry;
+    let limit = &settings.rate_limit;
+    let http = RecordingClient::new(
+        FailoverClient::new(client.http(), settings.failover.as_ref()),
+        settings.recording.as_ref(),
+    );
+    let endpoint = endpoint.with_middleware(middle);
+    let mut retry = 0;
+    loop {
+        if let Some(limit) = limit {
+            limit.acquire().await;
+        }
+        match endpoint.exec(&http).await {
+            Err(e) if retry < policy.max_retries && policy.should_retry(&endpoint.method(), &e) => {
+                let delay = policy.backoff(retry);
+                retry += 1;
+                warn!(
+                    "Request failed, retrying in {:?} ({}/{}): {}",
+                    delay, retry, policy.max_retries, e
+                );
+                tokio::time::sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+}
+
 /// Strips the wrapping information out of an [EndpointResult], returning the
 /// enclosing information as a [WrapInfo].
 fn strip_wrap<T>(result: EndpointResult<T>) -> Result<WrapInfo, ClientError> {
@@ -362,6 +684,20 @@ fn strip_wrap<T>(result: EndpointResult<T>) -> Result<WrapInfo, ClientError> {
     result.wrap_info.ok_or(ClientError::ResponseWrapError {})
 }
 
+/// Returns whether the `creation_path` of a wrapping token is the path of the
+/// endpoint which was wrapped. The creation path is prefixed with the
+/// namespace when the request was made in one.
+fn matches_path(creation_path: &str, namespace: Option<&str>, path: &str) -> bool {
+    let creation_path = creation_path.trim_matches('/');
+    let path = path.trim_matches('/');
+    let unprefixed = namespace
+        .map(|n| n.trim_matches('/'))
+        .filter(|n| !n.is_empty())
+        .and_then(|n| creation_path.strip_prefix(n))
+        .and_then(|p| p.strip_prefix('/'));
+    creation_path == path || unprefixed == Some(path)
+}
+
 /// Strips an [EndpointResult] off a response and logs any warnings found within
 fn strip<T>(result: EndpointResult<T>) -> Option<T>
 where
@@ -376,11 +712,40 @@ where
     result.data
 }
 
+/// Strips an [EndpointResult] off a response, keeping its lease information
+/// and warnings in a [Secret].
+fn strip_lease<T>(result: EndpointResult<T>) -> Result<Secret<T>, ClientError>
+where
+    T: DeserializeOwned,
+{
+    trace!("Stripping response wrapper from API response, keeping lease");
+    let warnings = result.warnings.unwrap_or_default();
+    if !warnings.is_empty() {
+        warn!("Detected warnings in API response: {:#?}", warnings);
+    }
+    Ok(Secret {
+        data: result.data.ok_or(ClientError::ResponseDataEmptyError)?,
+        lease_id: result.lease_id,
+        lease_duration: result.lease_duration.into(),
+        renewable: result.renewable,
+        request_id: result.request_id,
+        warnings,
+    })
+}
+
 /// Attempts to parse the enclosed API errors returned from a
 /// [rustify::errors::ClientError::ServerResponseError]. If errors can be parsed
 /// it returns the result as a [ClientError::APIError], otherwise it returns a
 /// [ClientError::RestClientError].
+/// Errors returned by a middleware are returned unchanged.
 fn parse_err(e: RestClientError) -> ClientError {
+    let e = match e {
+        RestClientError::GenericError { source } => match source.downcast::<ClientError>() {
+            Ok(e) => return e,
+            Err(source) => RestClientError::GenericError { source },
+        },
+        e => e,
+    };
     if let RestClientError::ServerResponseError { code, content } = &e {
         match content {
             Some(c) => {
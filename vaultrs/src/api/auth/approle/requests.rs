@@ -2,6 +2,7 @@ use super::responses::{
     CreateCustomSecretIDResponse, GenerateNewSecretIDResponse, ListRolesResponse,
     ListSecretIDResponse, ReadAppRoleResponse, ReadRoleIDResponse, ReadSecretIDResponse,
 };
+use crate::api::SecretString;
 use rustify_derive::Endpoint;
 
 /// ## Login with Approle
@@ -18,7 +19,7 @@ pub struct LoginWithApproleRequest {
     #[endpoint(skip)]
     pub mount: String,
     pub role_id: String,
-    pub secret_id: String,
+    pub secret_id: SecretString,
 }
 
 /// ## List Roles
@@ -228,7 +229,7 @@ pub struct ReadSecretIDRequest {
     pub mount: String,
     #[endpoint(skip)]
     pub role_name: String,
-    pub secret_id: String,
+    pub secret_id: SecretString,
 }
 
 /// ## Destroy AppRole Secret ID
@@ -250,7 +251,7 @@ pub struct DeleteSecretIDRequest {
     pub mount: String,
     #[endpoint(skip)]
     pub role_name: String,
-    pub secret_id: String,
+    pub secret_id: SecretString,
 }
 
 /// ## Read AppRole Secret ID Accessor
@@ -318,7 +319,7 @@ pub struct CreateCustomSecretIDRequest {
     pub mount: String,
     #[endpoint(skip)]
     pub role_name: String,
-    pub secret_id: String,
+    pub secret_id: SecretString,
     pub metadata: Option<String>,
     pub cidr_list: Option<Vec<String>>,
     pub token_bound_cidrs: Option<Vec<String>>,
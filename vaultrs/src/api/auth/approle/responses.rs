@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::api::SecretString;
+
 /// Response from executing
 /// [ListRolesRequest][crate::api::auth::approle::requests::ListRolesRequest]
 #[derive(Deserialize, Debug, Serialize)]
@@ -40,7 +42,7 @@ pub struct ReadRoleIDResponse {
 #[derive(Deserialize, Debug, Serialize)]
 pub struct GenerateNewSecretIDResponse {
     pub secret_id_accessor: String,
-    pub secret_id: String,
+    pub secret_id: SecretString,
     pub secret_id_ttl: u64,
 }
 
@@ -71,5 +73,5 @@ pub struct ReadSecretIDResponse {
 #[derive(Deserialize, Debug, Serialize)]
 pub struct CreateCustomSecretIDResponse {
     pub secret_id_accessor: String,
-    pub secret_id: String,
+    pub secret_id: SecretString,
 }
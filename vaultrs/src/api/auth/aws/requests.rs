@@ -6,6 +6,7 @@ use super::responses::{
     ReadIdentityConfigurationResponse, ReadRoleResponse, ReadRoleTagDenyListResponse,
     ReadRoleTagDenyListTidySettingsResponse, ReadStsRoleResponse, RotateRootCredentialsResponse,
 };
+use crate::api::SecretString;
 use rustify_derive::Endpoint;
 use serde::Serialize;
 
@@ -28,7 +29,7 @@ pub struct ConfigureClientRequest {
     pub mount: String,
     pub max_retries: Option<i64>,
     pub access_key: Option<String>,
-    pub secret_key: Option<String>,
+    pub secret_key: Option<SecretString>,
     pub endpoint: Option<String>,
     pub iam_endpoint: Option<String>,
     pub sts_endpoint: Option<String>,
@@ -1,6 +1,7 @@
 use super::responses::{
     ListRolesResponse, ReadKubernetesAuthConfigResponse, ReadKubernetesRoleResponse,
 };
+use crate::api::SecretString;
 use rustify_derive::Endpoint;
 
 /// ## Configure Kubernetes Auth
@@ -58,7 +59,7 @@ pub struct LoginWithKubernetesRequest {
     #[endpoint(skip)]
     pub mount: String,
     pub role: String,
-    pub jwt: String,
+    pub jwt: SecretString,
 }
 
 /// ## List Roles
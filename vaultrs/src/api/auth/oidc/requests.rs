@@ -1,6 +1,7 @@
 use super::responses::{
     ListRolesResponse, OIDCAuthResponse, ReadConfigurationResponse, ReadRoleResponse,
 };
+use crate::api::SecretString;
 use rustify_derive::Endpoint;
 use std::{collections::HashMap, fmt::Debug};
 
@@ -27,7 +28,7 @@ pub struct SetConfigurationRequest {
     pub oidc_discovery_ca_pem: Option<String>,
     pub oidc_discovery_url: Option<String>,
     pub oidc_client_id: Option<String>,
-    pub oidc_client_secret: Option<String>,
+    pub oidc_client_secret: Option<SecretString>,
     pub oidc_response_mode: Option<String>,
     pub oidc_response_types: Option<Vec<String>>,
     pub provider_config: Option<HashMap<String, String>>,
@@ -219,6 +220,6 @@ pub struct OIDCCallbackRequest {
 pub struct JWTLoginRequest {
     #[endpoint(skip)]
     pub mount: String,
-    pub jwt: String,
+    pub jwt: SecretString,
     pub role: Option<String>,
 }
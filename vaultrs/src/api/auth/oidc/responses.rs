@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::api::SecretString;
+
 /// Response from executing
 /// [ReadConfigurationRequest][crate::api::auth::oidc::requests::ReadConfigurationRequest]
 #[derive(Deserialize, Debug, Serialize)]
@@ -16,7 +18,7 @@ pub struct ReadConfigurationResponse {
     pub oidc_discovery_ca_pem: Option<String>,
     pub oidc_discovery_url: Option<String>,
     pub oidc_client_id: Option<String>,
-    pub oidc_client_secret: Option<String>,
+    pub oidc_client_secret: Option<SecretString>,
     pub oidc_response_mode: Option<String>,
     pub oidc_response_types: Option<Vec<String>>,
     pub provider_config: Option<HashMap<String, String>>,
@@ -1,4 +1,5 @@
 use super::responses::{ListUsersResponse, ReadUserResponse};
+use crate::api::SecretString;
 use rustify_derive::Endpoint;
 
 /// ## Create/Update User
@@ -20,7 +21,7 @@ pub struct CreateUserRequest {
     pub mount: String,
     #[endpoint(skip)]
     pub username: String,
-    pub password: String,
+    pub password: SecretString,
     pub token_bound_cidrs: Option<Vec<String>>,
     pub token_explicit_max_ttl: Option<String>,
     pub token_no_default_policy: Option<bool>,
@@ -93,7 +94,7 @@ pub struct UpdatePasswordRequest {
     pub mount: String,
     #[endpoint(skip)]
     pub username: String,
-    pub password: String,
+    pub password: SecretString,
 }
 
 /// ## Update Policies on User
@@ -157,5 +158,5 @@ pub struct LoginRequest {
     pub mount: String,
     #[endpoint(skip)]
     pub username: String,
-    pub password: String,
+    pub password: SecretString,
 }
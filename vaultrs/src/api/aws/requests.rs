@@ -1,3 +1,4 @@
+use crate::api::SecretString;
 use rustify_derive::Endpoint;
 use std::fmt::Debug;
 
@@ -22,7 +23,7 @@ pub struct SetConfigurationRequest {
     pub mount: String,
     pub max_retries: Option<i32>,
     pub access_key: String,
-    pub secret_key: String,
+    pub secret_key: SecretString,
     pub region: Option<String>,
     pub iam_endpoint: Option<String>,
     pub sts_endpoint: Option<String>,
@@ -1,3 +1,4 @@
+use crate::api::SecretString;
 use serde::{Deserialize, Serialize};
 
 /// Response from executing
@@ -50,7 +51,7 @@ pub struct ListRolesResponse {
 #[derive(Deserialize, Debug, Serialize)]
 pub struct GenerateCredentialsResponse {
     pub access_key: String,
-    pub secret_key: String,
-    pub security_token: Option<String>,
+    pub secret_key: SecretString,
+    pub security_token: Option<SecretString>,
     pub arn: String,
 }
@@ -3,6 +3,7 @@ use super::responses::{
     ListRolesResponse, ListStaticRolesResponse, ReadConnectionResponse, ReadRoleResponse,
     ReadStaticRoleResponse,
 };
+use crate::api::SecretString;
 use rustify_derive::Endpoint;
 use std::fmt::Debug;
 
@@ -34,7 +35,7 @@ pub struct PostgreSQLConnectionRequest {
     pub max_connection_lifetime: Option<String>, // PostgresSQL specific parameters
     pub max_idle_connections: Option<u64>,
     pub max_open_connections: Option<u64>,
-    pub password: Option<String>,
+    pub password: Option<SecretString>,
     pub username: Option<String>,
     pub username_template: Option<String>,
 }
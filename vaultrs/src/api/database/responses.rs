@@ -1,3 +1,4 @@
+use crate::api::SecretString;
 use serde::{Deserialize, Serialize};
 
 /// Response from executing
@@ -47,7 +48,7 @@ pub struct ListRolesResponse {
 #[derive(Deserialize, Debug, Serialize)]
 pub struct GenerateCredentialsResponse {
     pub username: String,
-    pub password: String,
+    pub password: SecretString,
 }
 
 /// Response from executing
@@ -72,7 +73,7 @@ pub struct ListStaticRolesResponse {
 #[derive(Deserialize, Debug, Serialize)]
 pub struct GetStaticCredentialsResponse {
     pub last_vault_rotation: String,
-    pub password: String,
+    pub password: SecretString,
     pub rotation_period: u64,
     pub ttl: u64,
     pub username: String,
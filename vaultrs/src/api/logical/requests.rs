@@ -0,0 +1,63 @@
+use rustify::endpoint::Endpoint;
+use rustify::enums::{RequestMethod, RequestType, ResponseType};
+use rustify::errors::ClientError;
+use rustify::http::build_query;
+use serde_json::Value;
+
+/// ## Logical Request
+/// This endpoint executes a request against an arbitrary path, allowing the
+/// use of endpoints which aren't modeled by this crate (e.g. plugins).
+///
+/// * Path: {self.path}
+/// * Method: {self.method}
+/// * Response: Value
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs>
+///
+/// `PATCH` requests are sent as a JSON merge patch, as expected by Vault.
+#[derive(Builder, Debug)]
+#[builder(setter(into))]
+pub struct LogicalRequest {
+    pub path: String,
+    pub method: RequestMethod,
+    #[builder(default, setter(strip_option))]
+    pub data: Option<Value>,
+    #[builder(default)]
+    pub query: Vec<(String, String)>,
+}
+
+impl LogicalRequest {
+    pub fn builder() -> LogicalRequestBuilder {
+        LogicalRequestBuilder::default()
+    }
+}
+
+impl Endpoint for LogicalRequest {
+    type Response = Value;
+    const REQUEST_BODY_TYPE: RequestType = RequestType::JSON;
+    const RESPONSE_BODY_TYPE: ResponseType = ResponseType::JSON;
+
+    fn path(&self) -> String {
+        self.path.trim_matches('/').to_string()
+    }
+
+    fn method(&self) -> RequestMethod {
+        self.method.clone()
+    }
+
+    fn query(&self) -> Result<Option<String>, ClientError> {
+        if self.query.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(build_query(&self.query)?))
+        }
+    }
+
+    fn body(&self) -> Result<Option<Vec<u8>>, ClientError> {
+        match &self.data {
+            Some(data) => serde_json::to_vec(data)
+                .map(Some)
+                .map_err(|e| ClientError::DataParseError { source: e.into() }),
+            None => Ok(None),
+        }
+    }
+}
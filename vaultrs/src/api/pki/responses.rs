@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::api::SecretString;
+
 /// Response from executing
 /// [GenerateCertificateRequest][crate::api::pki::requests::GenerateCertificateRequest]
 #[derive(Deserialize, Debug, Serialize)]
@@ -10,7 +12,7 @@ pub struct GenerateCertificateResponse {
     pub certificate: String,
     pub expiration: Option<u64>,
     pub issuing_ca: String,
-    pub private_key: String,
+    pub private_key: SecretString,
     pub private_key_type: String,
     pub serial_number: String,
 }
@@ -20,7 +22,7 @@ pub struct GenerateCertificateResponse {
 #[derive(Deserialize, Debug, Serialize)]
 pub struct GenerateIntermediateResponse {
     pub csr: String,
-    pub private_key: Option<String>,
+    pub private_key: Option<SecretString>,
     pub private_key_type: Option<String>,
 }
 
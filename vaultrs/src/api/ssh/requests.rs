@@ -7,6 +7,8 @@ use super::responses::{
 };
 use rustify_derive::Endpoint;
 
+use crate::api::SecretString;
+
 /// ## Create Role
 /// This endpoint creates or updates a named role.
 ///
@@ -260,7 +262,7 @@ pub struct SubmitCAInfoRequest {
     #[endpoint(skip)]
     pub mount: String,
     pub generate_signing_key: Option<bool>,
-    pub private_key: Option<String>,
+    pub private_key: Option<SecretString>,
     pub public_key: Option<String>,
 }
 
@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::api::SecretString;
+
 /// Response from executing
 /// [ReadRoleRequest][crate::api::ssh::requests::ReadRoleRequest]
 #[derive(Deserialize, Debug, Serialize)]
@@ -62,7 +64,7 @@ pub struct GenerateSSHCredsResponse {
     pub default_user: Option<String>,
     pub exclude_cidr_list: Option<String>,
     pub install_script: Option<String>,
-    pub key: String,
+    pub key: SecretString,
     pub key_bits: Option<u64>,
     pub key_option_specs: Option<String>,
     pub key_type: String,
@@ -1,2 +1,36 @@
 pub mod requests;
 pub mod responses;
+
+use serde::{Deserialize, Serialize};
+
+/// A capability a policy can grant on a path.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Capability {
+    /// Allows creating data at paths which don't exist yet (`POST`/`PUT`).
+    Create,
+    /// Allows reading the data at the path (`GET`).
+    Read,
+    /// Allows changing the data at existing paths (`POST`/`PUT`).
+    Update,
+    /// Allows partially updating the data at the path (`PATCH`).
+    Patch,
+    /// Allows deleting the data at the path (`DELETE`).
+    Delete,
+    /// Allows listing the keys under the path (`LIST`).
+    List,
+    /// Allows access to root-protected paths.
+    Sudo,
+    /// Disallows access, taking precedence over every other capability.
+    Deny,
+    /// Returned for root tokens, which are allowed every operation.
+    Root,
+    /// Allows subscribing to the events of the path.
+    Subscribe,
+    /// Allows recovering the data at the path from a snapshot.
+    Recover,
+    /// A capability this crate doesn't know about, returned by newer Vault
+    /// versions.
+    #[serde(other)]
+    Unknown,
+}
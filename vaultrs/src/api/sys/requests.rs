@@ -1,8 +1,13 @@
 use super::responses::{
-    AuthResponse, GetConfigurationOfTheSecretEngineResponse, ListPoliciesResponse, MountResponse,
-    RandomResponse, ReadHealthResponse, ReadPolicyResponse, RemountResponse, RemountStatusResponse,
-    StartInitializationResponse, UnsealResponse, WrappingLookupResponse,
+    AuditDeviceResponse, AuditHashResponse, AuthResponse, CapabilitiesResponse,
+    GenerateRootStatusResponse, GetConfigurationOfTheSecretEngineResponse, ListLeasesResponse,
+    ListPoliciesResponse, MountResponse, MountTuneResponse, RandomResponse, ReadHealthResponse,
+    ReadLeaseResponse, ReadPolicyResponse, RekeyBackupResponse, RekeyStatusResponse,
+    RekeyUpdateResponse, RekeyVerificationStatusResponse, RekeyVerifyResponse, RemountResponse,
+    RemountStatusResponse, RenewLeaseResponse, StartInitializationResponse, UnsealResponse,
+    WrappingLookupResponse,
 };
+use crate::api::SecretString;
 use rustify_derive::Endpoint;
 use serde::Serialize;
 use serde_json::Value;
@@ -29,19 +34,35 @@ pub struct EnableEngineRequest {
     pub options: Option<HashMap<String, String>>,
 }
 
+/// Configuration options of a mount, used when enabling and tuning both
+/// secret engines and auth methods.
 #[derive(Clone, Builder, Debug, Default, Serialize)]
 #[builder(setter(into, strip_option), default)]
-pub struct EnableEngineDataConfig {
+pub struct MountConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub default_lease_ttl: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_lease_ttl: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub force_no_cache: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub audit_non_hmac_request_keys: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub audit_non_hmac_response_keys: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub listing_visibility: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub passthrough_request_headers: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub allowed_response_headers: Option<Vec<String>>,
 }
 
+/// The configuration of a secret engine, see [MountConfig].
+pub type EnableEngineDataConfig = MountConfig;
+
+/// Builder for [EnableEngineDataConfig], see [MountConfigBuilder].
+pub type EnableEngineDataConfigBuilder = MountConfigBuilder;
+
 /// ## Disable Secrets Engine
 /// This endpoint disables the mount point specified in the URL.
 ///
@@ -79,6 +100,51 @@ pub struct GetConfigurationOfTheSecretEngineRequest {
     pub path: String,
 }
 
+/// ## Read Mount Configuration
+/// This endpoint reads the given mount's configuration.
+///
+/// * Path: sys/mounts/{self.path}/tune
+/// * Method: GET
+/// * Response: MountTuneResponse
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/mounts#read-mount-configuration>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "sys/mounts/{self.path}/tune",
+    response = "MountTuneResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadMountTuneRequest {
+    #[endpoint(skip)]
+    pub path: String,
+}
+
+/// ## Tune Mount Configuration
+/// This endpoint tunes configuration parameters for a given mount point.
+///
+/// * Path: sys/mounts/{self.path}/tune
+/// * Method: POST
+/// * Response: N/A
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/mounts#tune-mount-configuration>
+
+#[derive(Builder, Debug, Default, Endpoint, Serialize)]
+#[endpoint(
+    path = "sys/mounts/{self.path}/tune",
+    method = "POST",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct TuneMountRequest {
+    #[endpoint(skip)]
+    pub path: String,
+    #[serde(flatten)]
+    pub config: MountConfig,
+    pub description: Option<String>,
+    pub options: Option<HashMap<String, String>>,
+    pub plugin_version: Option<String>,
+}
+
 /// ## List Mounted Secrets Engines
 /// This endpoints lists all the mounted secrets engines.
 ///
@@ -132,17 +198,51 @@ pub struct DisableAuthRequest {
     pub path: String,
 }
 
-#[derive(Clone, Builder, Debug, Default, Serialize)]
+/// The configuration of an auth method, see [MountConfig].
+pub type EnableAuthDataConfig = MountConfig;
+
+/// Builder for [EnableAuthDataConfig], see [MountConfigBuilder].
+pub type EnableAuthDataConfigBuilder = MountConfigBuilder;
+
+/// ## Read Auth Method Tuning
+/// This endpoint reads the given auth path's configuration.
+///
+/// * Path: sys/auth/{self.path}/tune
+/// * Method: GET
+/// * Response: MountTuneResponse
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/auth#read-auth-method-tuning>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "sys/auth/{self.path}/tune",
+    response = "MountTuneResponse",
+    builder = "true"
+)]
 #[builder(setter(into, strip_option), default)]
-pub struct EnableAuthDataConfig {
-    pub default_lease_ttl: Option<String>,
-    pub max_lease_ttl: Option<String>,
-    pub force_no_cache: Option<bool>,
-    pub audit_non_hmac_request_keys: Option<Vec<String>>,
-    pub audit_non_hmac_response_keys: Option<Vec<String>>,
-    pub listing_visibility: Option<String>,
-    pub passthrough_request_headers: Option<Vec<String>>,
-    pub allowed_response_headers: Option<Vec<String>>,
+pub struct ReadAuthTuneRequest {
+    #[endpoint(skip)]
+    pub path: String,
+}
+
+/// ## Tune Auth Method
+/// This endpoint tunes configuration parameters for a given auth path.
+///
+/// * Path: sys/auth/{self.path}/tune
+/// * Method: POST
+/// * Response: N/A
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/auth#tune-auth-method>
+
+#[derive(Builder, Debug, Default, Endpoint, Serialize)]
+#[endpoint(path = "sys/auth/{self.path}/tune", method = "POST", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct TuneAuthRequest {
+    #[endpoint(skip)]
+    pub path: String,
+    #[serde(flatten)]
+    pub config: MountConfig,
+    pub description: Option<String>,
+    pub plugin_version: Option<String>,
+    pub token_type: Option<String>,
 }
 
 /// ## List Auth Methods
@@ -162,6 +262,178 @@ pub struct EnableAuthDataConfig {
 #[builder(setter(into, strip_option), default)]
 pub struct ListAuthsRequest {}
 
+/// ## Enable Audit Device
+/// This endpoint enables a new audit device at the supplied path.
+///
+/// * Path: sys/audit/{self.path}
+/// * Method: POST
+/// * Response: N/A
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/audit#enable-audit-device>
+
+#[derive(Builder, Debug, Default, Endpoint, Serialize)]
+#[endpoint(path = "sys/audit/{self.path}", method = "POST", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct EnableAuditDeviceRequest {
+    #[endpoint(skip)]
+    pub path: String,
+    #[serde(flatten)]
+    pub device: AuditDevice,
+    pub description: Option<String>,
+    pub local: Option<bool>,
+}
+
+/// The type of an audit device together with its options.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", content = "options", rename_all = "lowercase")]
+pub enum AuditDevice {
+    File(FileAuditDeviceOptions),
+    Syslog(SyslogAuditDeviceOptions),
+    Socket(SocketAuditDeviceOptions),
+}
+
+impl Default for AuditDevice {
+    fn default() -> Self {
+        AuditDevice::File(FileAuditDeviceOptions::default())
+    }
+}
+
+/// Options of a `file` audit device.
+///
+/// * Reference: <https://developer.hashicorp.com/vault/docs/audit/file>
+#[derive(Clone, Builder, Debug, Default, Serialize)]
+#[builder(setter(into, strip_option), default)]
+pub struct FileAuditDeviceOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elide_list_responses: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hmac_accessor: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_raw: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+}
+
+/// Options of a `syslog` audit device.
+///
+/// * Reference: <https://developer.hashicorp.com/vault/docs/audit/syslog>
+#[derive(Clone, Builder, Debug, Default, Serialize)]
+#[builder(setter(into, strip_option), default)]
+pub struct SyslogAuditDeviceOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facility: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elide_list_responses: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hmac_accessor: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_raw: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+}
+
+/// Options of a `socket` audit device.
+///
+/// * Reference: <https://developer.hashicorp.com/vault/docs/audit/socket>
+#[derive(Clone, Builder, Debug, Default, Serialize)]
+#[builder(setter(into, strip_option), default)]
+pub struct SocketAuditDeviceOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub socket_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_timeout: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elide_list_responses: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hmac_accessor: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_raw: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+}
+
+/// ## Disable Audit Device
+/// This endpoint disables the audit device at the given path.
+///
+/// * Path: sys/audit/{self.path}
+/// * Method: DELETE
+/// * Response: N/A
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/audit#disable-audit-device>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(path = "sys/audit/{self.path}", method = "DELETE", builder = "true")]
+#[builder(setter(into), default)]
+pub struct DisableAuditDeviceRequest {
+    #[endpoint(skip)]
+    pub path: String,
+}
+
+/// ## List Enabled Audit Devices
+/// This endpoint lists only the enabled audit devices.
+///
+/// * Path: sys/audit
+/// * Method: GET
+/// * Response: [HashMap<String, AuditDeviceResponse>]
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/audit#list-enabled-audit-devices>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "sys/audit",
+    response = "HashMap<String, AuditDeviceResponse>",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ListAuditDevicesRequest {}
+
+/// ## Calculate Hash
+/// This endpoint hashes the given input data with the specified audit
+/// device's hash function and salt. It can be used to discover whether a
+/// given plaintext string appears in the audit log in obfuscated form.
+///
+/// * Path: sys/audit-hash/{self.path}
+/// * Method: POST
+/// * Response: [AuditHashResponse]
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/audit-hash#calculate-hash>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "sys/audit-hash/{self.path}",
+    method = "POST",
+    response = "AuditHashResponse",
+    builder = "true"
+)]
+#[builder(setter(into), default)]
+pub struct AuditHashRequest {
+    #[endpoint(skip)]
+    pub path: String,
+    pub input: String,
+}
+
 /// ## Move backend
 ///
 /// The `/sys/remount` endpoint moves an already-mounted backend to a new mount point.
@@ -218,7 +490,7 @@ pub struct RemountStatusRequest {
 #[endpoint(path = "/sys/wrapping/unwrap", method = "POST", response = "Value")]
 #[builder(setter(into))]
 pub struct UnwrapRequest {
-    pub token: Option<String>,
+    pub token: Option<SecretString>,
 }
 
 /// ## Wrapping Lookup
@@ -238,7 +510,107 @@ pub struct UnwrapRequest {
 )]
 #[builder(setter(into), default)]
 pub struct WrappingLookupRequest {
-    pub token: String,
+    pub token: SecretString,
+}
+
+/// ## Wrapping Wrap
+/// This endpoint wraps the given user-supplied data inside a response-wrapped
+/// token.
+///
+/// * Path: /sys/wrapping/wrap
+/// * Method: POST
+/// * Response: N/A
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/wrapping-wrap>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(path = "/sys/wrapping/wrap", method = "POST", builder = "true")]
+#[builder(setter(into), default)]
+pub struct WrapRequest {
+    // Sent as the raw body, the wrapped data is the body itself rather than
+    // a field of it.
+    #[endpoint(raw)]
+    pub data: Vec<u8>,
+}
+
+/// ## Wrapping Rewrap
+/// This endpoint rewraps a response-wrapped token. The new token will use the
+/// same creation TTL as the original token and contain the same response.
+///
+/// * Path: /sys/wrapping/rewrap
+/// * Method: POST
+/// * Response: N/A
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/wrapping-rewrap>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(path = "/sys/wrapping/rewrap", method = "POST", builder = "true")]
+#[builder(setter(into), default)]
+pub struct RewrapRequest {
+    pub token: SecretString,
+}
+
+/// ## Query Token Capabilities
+/// This endpoint returns the list of capabilities of a given token on the
+/// given paths.
+///
+/// * Path: sys/capabilities
+/// * Method: POST
+/// * Response: CapabilitiesResponse
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/capabilities#query-token-capabilities>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "sys/capabilities",
+    method = "POST",
+    response = "CapabilitiesResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct CapabilitiesRequest {
+    pub paths: Vec<String>,
+    pub token: SecretString,
+}
+
+/// ## Query Self Capabilities
+/// This endpoint returns the capabilities of the client token on the given
+/// paths.
+///
+/// * Path: sys/capabilities-self
+/// * Method: POST
+/// * Response: CapabilitiesResponse
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/capabilities-self#query-self-capabilities>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "sys/capabilities-self",
+    method = "POST",
+    response = "CapabilitiesResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct CapabilitiesSelfRequest {
+    pub paths: Vec<String>,
+}
+
+/// ## Query Token Accessor Capabilities
+/// This endpoint returns the capabilities of the token associated with the
+/// given accessor on the given paths.
+///
+/// * Path: sys/capabilities-accessor
+/// * Method: POST
+/// * Response: CapabilitiesResponse
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/capabilities-accessor#query-token-accessor-capabilities>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "sys/capabilities-accessor",
+    method = "POST",
+    response = "CapabilitiesResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct CapabilitiesAccessorRequest {
+    pub accessor: String,
+    pub paths: Vec<String>,
 }
 
 /// ## Read Health Information
@@ -350,11 +722,258 @@ pub struct SealRequest {}
 )]
 #[builder(setter(into), default)]
 pub struct UnsealRequest {
-    pub key: Option<String>,
+    pub key: Option<SecretString>,
     pub reset: Option<bool>,
     pub migrate: Option<bool>,
 }
 
+/// ## Read Rekey Progress
+/// This endpoint reads the configuration and progress of the current rekey
+/// attempt.
+///
+/// * Path: sys/rekey/init
+/// * Method: GET
+/// * Response: [RekeyStatusResponse]
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/rekey#read-rekey-progress>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "sys/rekey/init",
+    response = "RekeyStatusResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadRekeyProgressRequest {}
+
+/// ## Start Rekey
+/// This endpoint initializes a new rekey attempt. Only a single rekey attempt
+/// can take place at a time, and changing the parameters of a rekey requires
+/// canceling and starting a new rekey.
+///
+/// * Path: sys/rekey/init
+/// * Method: POST
+/// * Response: [RekeyStatusResponse]
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/rekey#start-rekey>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "sys/rekey/init",
+    method = "POST",
+    response = "RekeyStatusResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct StartRekeyRequest {
+    pub secret_shares: u64,
+    pub secret_threshold: u64,
+    pub pgp_keys: Option<Vec<String>>,
+    pub backup: Option<bool>,
+    pub require_verification: Option<bool>,
+}
+
+/// ## Cancel Rekey
+/// This endpoint cancels any in-progress rekey.
+///
+/// * Path: sys/rekey/init
+/// * Method: DELETE
+/// * Response: N/A
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/rekey#cancel-rekey>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(path = "sys/rekey/init", method = "DELETE", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct CancelRekeyRequest {}
+
+/// ## Submit Rekey Key
+/// This endpoint is used to enter a single root key share to progress the
+/// rekey of the Vault.
+///
+/// * Path: sys/rekey/update
+/// * Method: POST
+/// * Response: [RekeyUpdateResponse]
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/rekey#submit-key>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "sys/rekey/update",
+    method = "POST",
+    response = "RekeyUpdateResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct RekeyUpdateRequest {
+    pub key: SecretString,
+    pub nonce: String,
+}
+
+/// ## Read Rekey Backup Key
+/// This endpoint returns the backup copy of PGP-encrypted unseal keys.
+///
+/// * Path: sys/rekey/backup
+/// * Method: GET
+/// * Response: [RekeyBackupResponse]
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/rekey#read-backup-key>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "sys/rekey/backup",
+    response = "RekeyBackupResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadRekeyBackupRequest {}
+
+/// ## Delete Rekey Backup Key
+/// This endpoint deletes the backup copy of PGP-encrypted unseal keys.
+///
+/// * Path: sys/rekey/backup
+/// * Method: DELETE
+/// * Response: N/A
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/rekey#delete-backup-key>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(path = "sys/rekey/backup", method = "DELETE", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct DeleteRekeyBackupRequest {}
+
+/// ## Read Rekey Verification Progress
+/// This endpoint reads the progress of the current rekey verification
+/// attempt.
+///
+/// * Path: sys/rekey/verify
+/// * Method: GET
+/// * Response: [RekeyVerificationStatusResponse]
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/rekey#read-rekey-verification-progress>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "sys/rekey/verify",
+    response = "RekeyVerificationStatusResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadRekeyVerificationProgressRequest {}
+
+/// ## Cancel Rekey Verification
+/// This endpoint cancels any in-progress rekey verification and resets it,
+/// returning the progress of the new verification attempt.
+///
+/// * Path: sys/rekey/verify
+/// * Method: DELETE
+/// * Response: [RekeyVerificationStatusResponse]
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/rekey#cancel-rekey-verification>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "sys/rekey/verify",
+    method = "DELETE",
+    response = "RekeyVerificationStatusResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct CancelRekeyVerificationRequest {}
+
+/// ## Submit Rekey Verification Key
+/// This endpoint is used to enter a single new key share to progress the
+/// rekey verification operation.
+///
+/// * Path: sys/rekey/verify
+/// * Method: POST
+/// * Response: [RekeyVerifyResponse]
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/rekey#submit-verification-key>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "sys/rekey/verify",
+    method = "POST",
+    response = "RekeyVerifyResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct RekeyVerifyRequest {
+    pub key: SecretString,
+    pub nonce: String,
+}
+
+/// ## Read Root Generation Progress
+/// This endpoint reads the configuration and process of the current root
+/// generation attempt.
+///
+/// * Path: sys/generate-root/attempt
+/// * Method: GET
+/// * Response: [GenerateRootStatusResponse]
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/generate-root#read-root-generation-progress>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "sys/generate-root/attempt",
+    response = "GenerateRootStatusResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct ReadGenerateRootProgressRequest {}
+
+/// ## Start Root Token Generation
+/// This endpoint initializes a new root generation attempt. Only a single root
+/// generation attempt can take place at a time. Unless a PGP key is given, the
+/// response contains the OTP needed to decode the generated token.
+///
+/// * Path: sys/generate-root/attempt
+/// * Method: POST
+/// * Response: [GenerateRootStatusResponse]
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/generate-root#start-root-token-generation>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "sys/generate-root/attempt",
+    method = "POST",
+    response = "GenerateRootStatusResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct StartGenerateRootRequest {
+    pub pgp_key: Option<String>,
+}
+
+/// ## Cancel Root Generation
+/// This endpoint cancels any in-progress root generation attempt.
+///
+/// * Path: sys/generate-root/attempt
+/// * Method: DELETE
+/// * Response: N/A
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/generate-root#cancel-root-generation>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "sys/generate-root/attempt",
+    method = "DELETE",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct CancelGenerateRootRequest {}
+
+/// ## Provide Key Share to Generate Root
+/// This endpoint is used to enter a single root key share to progress the
+/// root generation attempt.
+///
+/// * Path: sys/generate-root/update
+/// * Method: POST
+/// * Response: [GenerateRootStatusResponse]
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/generate-root#provide-key-share-to-generate-root>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "sys/generate-root/update",
+    method = "POST",
+    response = "GenerateRootStatusResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct GenerateRootUpdateRequest {
+    pub key: SecretString,
+    pub nonce: String,
+}
+
 /// ## List Policies
 /// This endpoint lists all configured policies.
 ///
@@ -443,3 +1062,137 @@ pub struct RandomRequest {
     pub format: Option<String>,
     pub source: Option<String>,
 }
+
+/// ## Read Lease
+/// This endpoint retrieves lease metadata.
+///
+/// * Path: /sys/leases/lookup
+/// * Method: PUT
+/// * Response: [ReadLeaseResponse]
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/leases#read-lease>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "/sys/leases/lookup",
+    method = "PUT",
+    response = "ReadLeaseResponse",
+    builder = "true"
+)]
+#[builder(setter(into), default)]
+pub struct ReadLeaseRequest {
+    pub lease_id: String,
+}
+
+/// ## List Leases
+/// This endpoint returns a list of lease ids under the given prefix.
+///
+/// * Path: /sys/leases/lookup/{self.prefix}
+/// * Method: LIST
+/// * Response: [ListLeasesResponse]
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/leases#list-leases>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "/sys/leases/lookup/{self.prefix}",
+    method = "LIST",
+    response = "ListLeasesResponse",
+    builder = "true"
+)]
+#[builder(setter(into), default)]
+pub struct ListLeasesRequest {
+    #[endpoint(skip)]
+    pub prefix: String,
+}
+
+/// ## Renew Lease
+/// This endpoint renews a lease, requesting to extend the lease.
+///
+/// * Path: /sys/leases/renew
+/// * Method: PUT
+/// * Response: [RenewLeaseResponse]
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/leases#renew-lease>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "/sys/leases/renew",
+    method = "PUT",
+    response = "RenewLeaseResponse",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct RenewLeaseRequest {
+    pub lease_id: String,
+    pub increment: Option<String>,
+}
+
+/// ## Revoke Lease
+/// This endpoint revokes a lease immediately.
+///
+/// * Path: /sys/leases/revoke
+/// * Method: PUT
+/// * Response: N/A
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/leases#revoke-lease>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(path = "/sys/leases/revoke", method = "PUT", builder = "true")]
+#[builder(setter(into, strip_option), default)]
+pub struct RevokeLeaseRequest {
+    pub lease_id: String,
+    pub sync: Option<bool>,
+}
+
+/// ## Revoke Prefix
+/// This endpoint revokes all secrets (via a lease ID prefix) or tokens (via
+/// the tokens' path property) generated under a given prefix immediately.
+///
+/// * Path: /sys/leases/revoke-prefix/{self.prefix}
+/// * Method: PUT
+/// * Response: N/A
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/leases#revoke-prefix>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "/sys/leases/revoke-prefix/{self.prefix}",
+    method = "PUT",
+    builder = "true"
+)]
+#[builder(setter(into, strip_option), default)]
+pub struct RevokePrefixRequest {
+    #[endpoint(skip)]
+    pub prefix: String,
+    pub sync: Option<bool>,
+}
+
+/// ## Revoke Force
+/// This endpoint revokes all secrets or tokens generated under a given
+/// prefix immediately, ignoring any errors returned by the backend.
+///
+/// * Path: /sys/leases/revoke-force/{self.prefix}
+/// * Method: PUT
+/// * Response: N/A
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/leases#revoke-force>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(
+    path = "/sys/leases/revoke-force/{self.prefix}",
+    method = "PUT",
+    builder = "true"
+)]
+#[builder(setter(into), default)]
+pub struct RevokeForceRequest {
+    #[endpoint(skip)]
+    pub prefix: String,
+}
+
+/// ## Tidy Leases
+/// This endpoint cleans up the dangling storage entries for leases.
+///
+/// * Path: /sys/leases/tidy
+/// * Method: POST
+/// * Response: N/A
+/// * Reference: <https://developer.hashicorp.com/vault/api-docs/system/leases#tidy-leases>
+
+#[derive(Builder, Debug, Default, Endpoint)]
+#[endpoint(path = "/sys/leases/tidy", method = "POST", builder = "true")]
+#[builder(setter(into), default)]
+pub struct TidyLeasesRequest {}
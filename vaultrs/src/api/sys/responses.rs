@@ -2,6 +2,9 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use super::Capability;
+use crate::api::SecretString;
+
 /// Response from executing
 /// [ListMountsRequest][crate::api::sys::requests::ListMountsRequest]
 #[derive(Deserialize, Debug, Serialize)]
@@ -46,6 +49,25 @@ pub struct GetConfigurationOfTheSecretEngineResponse {
     pub uuid: String,
 }
 
+/// Response from executing
+/// [ReadMountTuneRequest][crate::api::sys::requests::ReadMountTuneRequest] or
+/// [ReadAuthTuneRequest][crate::api::sys::requests::ReadAuthTuneRequest]
+#[derive(Deserialize, Debug, Serialize)]
+pub struct MountTuneResponse {
+    pub default_lease_ttl: u64,
+    pub max_lease_ttl: u64,
+    pub force_no_cache: bool,
+    pub description: Option<String>,
+    pub audit_non_hmac_request_keys: Option<Vec<String>>,
+    pub audit_non_hmac_response_keys: Option<Vec<String>>,
+    pub listing_visibility: Option<String>,
+    pub passthrough_request_headers: Option<Vec<String>>,
+    pub allowed_response_headers: Option<Vec<String>>,
+    pub options: Option<HashMap<String, String>>,
+    pub plugin_version: Option<String>,
+    pub token_type: Option<String>,
+}
+
 /// Response from executing
 /// [ListAuthsRequest][crate::api::sys::requests::ListAuthsRequest]
 #[derive(Deserialize, Debug, Serialize)]
@@ -72,6 +94,25 @@ pub struct AuthConfigResponse {
     pub token_type: String,
 }
 
+/// Response from executing
+/// [ListAuditDevicesRequest][crate::api::sys::requests::ListAuditDevicesRequest]
+#[derive(Deserialize, Debug, Serialize)]
+pub struct AuditDeviceResponse {
+    pub description: String,
+    pub local: bool,
+    pub options: Option<HashMap<String, String>>,
+    pub path: String,
+    #[serde(rename = "type")]
+    pub device_type: String,
+}
+
+/// Response from executing
+/// [AuditHashRequest][crate::api::sys::requests::AuditHashRequest]
+#[derive(Deserialize, Debug, Serialize)]
+pub struct AuditHashResponse {
+    pub hash: String,
+}
+
 /// Response from executing
 /// [reMountRequest][crate::api::sys::requests::ReMountRequest]
 #[derive(Deserialize, Debug, Serialize)]
@@ -103,6 +144,21 @@ pub struct WrappingLookupResponse {
     pub creation_ttl: u64,
 }
 
+/// Response from executing
+/// [CapabilitiesRequest][crate::api::sys::requests::CapabilitiesRequest],
+/// [CapabilitiesSelfRequest][crate::api::sys::requests::CapabilitiesSelfRequest]
+/// or
+/// [CapabilitiesAccessorRequest][crate::api::sys::requests::CapabilitiesAccessorRequest]
+#[derive(Deserialize, Debug, Serialize)]
+pub struct CapabilitiesResponse {
+    /// The capabilities on the requested path, only set when a single path
+    /// was queried.
+    pub capabilities: Option<Vec<Capability>>,
+    /// The capabilities on each of the requested paths.
+    #[serde(flatten)]
+    pub paths: HashMap<String, Vec<Capability>>,
+}
+
 /// Response from executing
 /// [ReadHealthRequest][crate::api::sys::requests::ReadHealthRequest]
 #[derive(Deserialize, Debug, Serialize)]
@@ -134,9 +190,9 @@ pub struct ReadHealthResponse {
 /// [StartInitializationRequest][crate::api::sys::requests::StartInitializationRequest]
 #[derive(Deserialize, Debug, Serialize)]
 pub struct StartInitializationResponse {
-    pub keys: Vec<String>,
-    pub keys_base64: Vec<String>,
-    pub root_token: String,
+    pub keys: Vec<SecretString>,
+    pub keys_base64: Vec<SecretString>,
+    pub root_token: SecretString,
 }
 
 /// Response from executing
@@ -154,6 +210,101 @@ pub struct UnsealResponse {
     pub cluster_id: Option<String>,
 }
 
+/// Response from executing
+/// [ReadRekeyProgressRequest][crate::api::sys::requests::ReadRekeyProgressRequest]
+/// or [StartRekeyRequest][crate::api::sys::requests::StartRekeyRequest]
+#[derive(Deserialize, Debug, Serialize)]
+pub struct RekeyStatusResponse {
+    pub started: bool,
+    pub nonce: String,
+    #[serde(rename = "t")]
+    pub threshold: u64,
+    #[serde(rename = "n")]
+    pub n_shares: u64,
+    pub progress: u64,
+    pub required: u64,
+    pub pgp_fingerprints: Option<Vec<String>>,
+    pub backup: bool,
+    pub verification_required: bool,
+}
+
+/// Response from executing
+/// [RekeyUpdateRequest][crate::api::sys::requests::RekeyUpdateRequest]
+///
+/// Until enough key shares were submitted only the progress is set, the new
+/// keys are returned once `complete` is `true`.
+#[derive(Deserialize, Debug, Serialize)]
+pub struct RekeyUpdateResponse {
+    pub nonce: String,
+    #[serde(default)]
+    pub complete: bool,
+    pub progress: Option<u64>,
+    pub required: Option<u64>,
+    pub keys: Option<Vec<SecretString>>,
+    pub keys_base64: Option<Vec<SecretString>>,
+    pub pgp_fingerprints: Option<Vec<String>>,
+    #[serde(default)]
+    pub backup: bool,
+    #[serde(default)]
+    pub verification_required: bool,
+    pub verification_nonce: Option<String>,
+}
+
+/// Response from executing
+/// [ReadRekeyBackupRequest][crate::api::sys::requests::ReadRekeyBackupRequest]
+#[derive(Deserialize, Debug, Serialize)]
+pub struct RekeyBackupResponse {
+    pub nonce: String,
+    pub keys: HashMap<String, Vec<SecretString>>,
+    pub keys_base64: Option<HashMap<String, Vec<SecretString>>>,
+}
+
+/// Response from executing
+/// [ReadRekeyVerificationProgressRequest][crate::api::sys::requests::ReadRekeyVerificationProgressRequest]
+/// or
+/// [CancelRekeyVerificationRequest][crate::api::sys::requests::CancelRekeyVerificationRequest]
+#[derive(Deserialize, Debug, Serialize)]
+pub struct RekeyVerificationStatusResponse {
+    pub started: bool,
+    pub nonce: String,
+    #[serde(rename = "t")]
+    pub threshold: u64,
+    #[serde(rename = "n")]
+    pub n_shares: u64,
+    pub progress: u64,
+}
+
+/// Response from executing
+/// [RekeyVerifyRequest][crate::api::sys::requests::RekeyVerifyRequest]
+#[derive(Deserialize, Debug, Serialize)]
+pub struct RekeyVerifyResponse {
+    pub nonce: String,
+    #[serde(default)]
+    pub complete: bool,
+    pub progress: Option<u64>,
+}
+
+/// Response from executing
+/// [ReadGenerateRootProgressRequest][crate::api::sys::requests::ReadGenerateRootProgressRequest],
+/// [StartGenerateRootRequest][crate::api::sys::requests::StartGenerateRootRequest]
+/// or
+/// [GenerateRootUpdateRequest][crate::api::sys::requests::GenerateRootUpdateRequest]
+#[derive(Deserialize, Debug, Serialize)]
+pub struct GenerateRootStatusResponse {
+    pub started: bool,
+    pub nonce: String,
+    pub progress: u64,
+    pub required: u64,
+    pub complete: bool,
+    /// The generated token, encoded with the OTP or encrypted with the PGP
+    /// key. Empty until the attempt is complete.
+    pub encoded_token: Option<String>,
+    pub pgp_fingerprint: Option<String>,
+    /// Only returned when starting an attempt without a PGP key.
+    pub otp: Option<SecretString>,
+    pub otp_length: u64,
+}
+
 /// Response from executing
 /// [ListPoliciesRequest][crate::api::sys::requests::ListPoliciesRequest]
 #[derive(Deserialize, Debug, Serialize)]
@@ -175,3 +326,31 @@ pub struct ReadPolicyResponse {
 pub struct RandomResponse {
     pub random_bytes: String,
 }
+
+/// Response from executing
+/// [ReadLeaseRequest][crate::api::sys::requests::ReadLeaseRequest]
+#[derive(Deserialize, Debug, Serialize)]
+pub struct ReadLeaseResponse {
+    pub id: String,
+    pub issue_time: String,
+    pub expire_time: Option<String>,
+    pub last_renewal: Option<String>,
+    pub renewable: bool,
+    pub ttl: u64,
+}
+
+/// Response from executing
+/// [ListLeasesRequest][crate::api::sys::requests::ListLeasesRequest]
+#[derive(Deserialize, Debug, Serialize)]
+pub struct ListLeasesResponse {
+    pub keys: Vec<String>,
+}
+
+/// Response from executing
+/// [RenewLeaseRequest][crate::api::sys::requests::RenewLeaseRequest]
+#[derive(Deserialize, Debug, Serialize)]
+pub struct RenewLeaseResponse {
+    pub lease_id: String,
+    pub renewable: bool,
+    pub lease_duration: u64,
+}
@@ -1,6 +1,7 @@
 use super::responses::{
     ListAccessorResponse, ListTokenRolesResponse, LookupTokenResponse, ReadTokenRoleResponse,
 };
+use crate::api::SecretString;
 use rustify_derive::Endpoint;
 use serde::Serialize;
 use std::{collections::HashMap, fmt::Debug};
@@ -129,7 +130,7 @@ pub struct CreateRoleTokenRequest {
 )]
 #[builder(setter(into, strip_option), default)]
 pub struct LookupTokenRequest {
-    pub token: String,
+    pub token: SecretString,
 }
 
 /// ## Lookup a Token (Self)
@@ -178,7 +179,7 @@ pub struct LookupTokenAccessorRequest {
 #[endpoint(path = "/auth/token/renew", method = "POST", builder = "true")]
 #[builder(setter(into, strip_option), default)]
 pub struct RenewTokenRequest {
-    pub token: String,
+    pub token: SecretString,
     pub increment: Option<String>,
 }
 
@@ -222,7 +223,7 @@ pub struct RenewTokenAccessorRequest {
 #[endpoint(path = "/auth/token/revoke", method = "POST", builder = "true")]
 #[builder(setter(into, strip_option), default)]
 pub struct RevokeTokenRequest {
-    pub token: String,
+    pub token: SecretString,
 }
 
 /// ## Revoke a Token (Self)
@@ -266,7 +267,7 @@ pub struct RevokeTokenAccessorRequest {
 #[endpoint(path = "/auth/token/revoke-orphan", method = "POST", builder = "true")]
 #[builder(setter(into, strip_option), default)]
 pub struct RevokeTokenOrphanRequest {
-    pub token: String,
+    pub token: SecretString,
 }
 
 /// ## Read Token Role
@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::api::SecretString;
+
 /// Response from executing
 /// [ListAccessorRequest][crate::api::token::requests::ListAccessorRequest]
 #[derive(Deserialize, Debug, Serialize)]
@@ -20,7 +22,7 @@ pub struct LookupTokenResponse {
     pub entity_id: String,
     pub expire_time: Option<String>,
     pub explicit_max_ttl: u64,
-    pub id: String,
+    pub id: SecretString,
     pub identity_policies: Option<Vec<String>>,
     pub issue_time: Option<String>,
     pub meta: Option<HashMap<String, String>>,
@@ -107,6 +107,7 @@ pub mod roles {
                 },
                 responses::{GenerateCredentialsResponse, ListRolesResponse, ReadRoleResponse},
             },
+            Secret,
         },
         client::Client,
         error::ClientError,
@@ -179,6 +180,25 @@ pub mod roles {
         api::exec_with_result(client, endpoint).await
     }
 
+    /// Generate credentials using /aws/creds endpoint, returning them with
+    /// their lease
+    pub async fn credentials_with_lease(
+        client: &impl Client,
+        mount: &str,
+        name: &str,
+        opts: Option<&mut GenerateCredentialsRequestBuilder>,
+    ) -> Result<Secret<GenerateCredentialsResponse>, ClientError> {
+        let mut t = GenerateCredentialsRequest::builder();
+        let endpoint = opts
+            .unwrap_or(&mut t)
+            .mount(mount)
+            .name(name)
+            .build()
+            .unwrap();
+
+        api::exec_with_lease(client, endpoint).await
+    }
+
     /// Generate credentials using /aws/sts endpoint
     pub async fn credentials_sts(
         client: &impl Client,
@@ -196,4 +216,23 @@ pub mod roles {
 
         api::exec_with_result(client, endpoint).await
     }
+
+    /// Generate credentials using /aws/sts endpoint, returning them with their
+    /// lease
+    pub async fn credentials_sts_with_lease(
+        client: &impl Client,
+        mount: &str,
+        name: &str,
+        opts: Option<&mut GenerateCredentialsStsRequestBuilder>,
+    ) -> Result<Secret<GenerateCredentialsResponse>, ClientError> {
+        let mut t = GenerateCredentialsStsRequest::builder();
+        let endpoint = opts
+            .unwrap_or(&mut t)
+            .mount(mount)
+            .name(name)
+            .build()
+            .unwrap();
+
+        api::exec_with_lease(client, endpoint).await
+    }
 }
@@ -0,0 +1,175 @@
+//! A synchronous API for programs which don't run an async runtime.
+//!
+//! The [VaultClient] of this module wraps an async
+//! [VaultClient][crate::client::VaultClient] together with a single threaded
+//! runtime, and every request is driven to completion on that runtime. The
+//! functions of the submodules mirror the ones of the top level modules and
+//! take the same [request types][crate::api] and builders:
+//!
+//! ```no_run
+//! use std::collections::HashMap;
+//!
+//! use vaultrs::blocking::{kv2, VaultClient};
+//! use vaultrs::client::VaultClientSettingsBuilder;
+//!
+//! let client = VaultClient::new(
+//!     VaultClientSettingsBuilder::default()
+//!         .address("https://127.0.0.1:8200")
+//!         .token("TOKEN")
+//!         .build()
+//!         .unwrap(),
+//! )
+//! .unwrap();
+//!
+//! let secret: HashMap<String, String> = kv2::read(&client, "secret", "mysecret").unwrap();
+//! ```
+//!
+//! Functions without a blocking version can be run with [Client::block_on]:
+//!
+//! ```no_run
+//! use vaultrs::blocking::{Client, VaultClient};
+//! use vaultrs::client::VaultClientSettingsBuilder;
+//! use vaultrs::ssh;
+//!
+//! let client = VaultClient::new(VaultClientSettingsBuilder::default().build().unwrap()).unwrap();
+//! let roles = client.block_on(ssh::role::list(client.inner(), "ssh")).unwrap();
+//! ```
+//!
+//! The blocking client can't be used from within an async runtime: calling it
+//! from an async task panics.
+
+use std::future::Future;
+
+use tokio::runtime::Runtime;
+
+use crate::api::sys::Capability;
+use crate::api::token::responses::LookupTokenResponse;
+use crate::api::{AuthInfo, SecretString};
+use crate::client::{Client as AsyncClient, VaultClientSettings};
+use crate::error::ClientError;
+use crate::sys::ServerStatus;
+
+/// Defines blocking versions of async functions taking a
+/// [Client][crate::client::Client] as their first argument.
+macro_rules! blocking {
+    ($(
+        $(#[$attr:meta])*
+        fn $name:ident$(<$generic:ident: $bound:path>)?($($arg:ident: $ty:ty),* $(,)?)
+            -> $ret:ty = $path:path;
+    )*) => {
+        $(
+            #[doc = concat!("Blocking version of [", stringify!($path), "].")]
+            $(#[$attr])*
+            pub fn $name$(<$generic: $bound>)?(
+                client: &impl $crate::blocking::Client,
+                $($arg: $ty),*
+            ) -> Result<$ret, $crate::error::ClientError> {
+                client.block_on($path(client.inner(), $($arg),*))
+            }
+        )*
+    };
+}
+
+pub mod kv1;
+pub mod kv2;
+pub mod pki;
+pub mod sys;
+pub mod token;
+pub mod transit;
+
+/// The synchronous equivalent of [Client][crate::client::Client].
+pub trait Client: Sized {
+    /// The async client requests are sent through.
+    type Inner: AsyncClient;
+
+    /// Returns the async client requests are sent through.
+    fn inner(&self) -> &Self::Inner;
+
+    /// Returns a mutable reference to the async client requests are sent
+    /// through.
+    fn inner_mut(&mut self) -> &mut Self::Inner;
+
+    /// Runs the given future to completion, blocking the current thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within an async runtime.
+    fn block_on<F: Future>(&self, future: F) -> F::Output;
+
+    /// Returns the current token of the client.
+    fn token(&self) -> SecretString {
+        self.inner().token()
+    }
+
+    /// Sets the token of the client.
+    fn set_token(&mut self, token: &str) {
+        self.inner_mut().set_token(token)
+    }
+
+    /// Replaces the token of the client, and of every client sharing it.
+    fn swap_token(&self, token: &str) {
+        self.inner().swap_token(token)
+    }
+
+    /// Looks up the current token being used by this client
+    fn lookup(&self) -> Result<LookupTokenResponse, ClientError> {
+        self.block_on(self.inner().lookup())
+    }
+
+    /// Renews the current token being used by this client
+    fn renew(&self, increment: Option<&str>) -> Result<AuthInfo, ClientError> {
+        self.block_on(self.inner().renew(increment))
+    }
+
+    /// Revokes the current token being used by this client
+    fn revoke(&self) -> Result<(), ClientError> {
+        self.block_on(self.inner().revoke())
+    }
+
+    /// Returns the status of the configured Vault server
+    fn status(&self) -> Result<ServerStatus, ClientError> {
+        self.block_on(self.inner().status())
+    }
+
+    /// Returns the capabilities of the current token on the given path.
+    fn capabilities(&self, path: &str) -> Result<Vec<Capability>, ClientError> {
+        self.block_on(self.inner().capabilities(path))
+    }
+}
+
+/// A client which blocks the current thread while executing calls against a
+/// Vault server.
+///
+/// The client owns its runtime, so connections are reused between calls.
+pub struct VaultClient {
+    client: crate::client::VaultClient,
+    runtime: Runtime,
+}
+
+impl VaultClient {
+    /// Creates a new [VaultClient] using the given [VaultClientSettings].
+    pub fn new(settings: VaultClientSettings) -> Result<VaultClient, ClientError> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| ClientError::RuntimeBuildError { source: e })?;
+        let client = crate::client::VaultClient::new(settings)?;
+        Ok(VaultClient { client, runtime })
+    }
+}
+
+impl Client for VaultClient {
+    type Inner = crate::client::VaultClient;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.client
+    }
+
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        &mut self.client
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+}
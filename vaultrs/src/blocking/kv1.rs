@@ -0,0 +1,13 @@
+use std::collections::HashMap;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::api::kv1::responses::{GetSecretResponse, ListSecretResponse};
+
+blocking! {
+    fn set<T: Serialize>(mount: &str, path: &str, data: &HashMap<&str, T>) -> () = crate::kv1::set;
+    fn get<D: DeserializeOwned>(mount: &str, path: &str) -> D = crate::kv1::get;
+    fn get_raw(mount: &str, path: &str) -> GetSecretResponse = crate::kv1::get_raw;
+    fn list(mount: &str, path: &str) -> ListSecretResponse = crate::kv1::list;
+    fn delete(mount: &str, path: &str) -> () = crate::kv1::delete;
+}
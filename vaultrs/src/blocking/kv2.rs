@@ -0,0 +1,67 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::api::kv2::{
+    requests::{SetSecretMetadataRequestBuilder, SetSecretRequestOptions},
+    responses::{ReadSecretMetadataResponse, SecretVersionMetadata},
+};
+
+blocking! {
+    fn delete_latest(mount: &str, path: &str) -> () = crate::kv2::delete_latest;
+    fn delete_metadata(mount: &str, path: &str) -> () = crate::kv2::delete_metadata;
+    fn delete_versions(
+        mount: &str,
+        path: &str,
+        versions: Vec<u64>,
+    ) -> () = crate::kv2::delete_versions;
+    fn destroy_versions(
+        mount: &str,
+        path: &str,
+        versions: Vec<u64>,
+    ) -> () = crate::kv2::destroy_versions;
+    fn list(mount: &str, path: &str) -> Vec<String> = crate::kv2::list;
+    fn read<D: DeserializeOwned>(mount: &str, path: &str) -> D = crate::kv2::read;
+    fn read_metadata(
+        mount: &str,
+        path: &str,
+    ) -> ReadSecretMetadataResponse = crate::kv2::read_metadata;
+    fn read_version<D: DeserializeOwned>(
+        mount: &str,
+        path: &str,
+        version: u64,
+    ) -> D = crate::kv2::read_version;
+    fn set<T: Serialize>(
+        mount: &str,
+        path: &str,
+        data: &T,
+    ) -> SecretVersionMetadata = crate::kv2::set;
+    fn set_with_options<T: Serialize>(
+        mount: &str,
+        path: &str,
+        data: &T,
+        options: SetSecretRequestOptions,
+    ) -> SecretVersionMetadata = crate::kv2::set_with_options;
+    fn set_metadata(
+        mount: &str,
+        path: &str,
+        opts: Option<&mut SetSecretMetadataRequestBuilder>,
+    ) -> () = crate::kv2::set_metadata;
+    fn undelete_versions(
+        mount: &str,
+        path: &str,
+        versions: Vec<u64>,
+    ) -> () = crate::kv2::undelete_versions;
+}
+
+pub mod config {
+    use crate::api::kv2::{
+        requests::SetConfigurationRequestBuilder, responses::ReadConfigurationResponse,
+    };
+
+    blocking! {
+        fn read(mount: &str) -> ReadConfigurationResponse = crate::kv2::config::read;
+        fn set(
+            mount: &str,
+            opts: Option<&mut SetConfigurationRequestBuilder>,
+        ) -> () = crate::kv2::config::set;
+    }
+}
@@ -0,0 +1,204 @@
+pub mod cert {
+    use crate::api::pki::{
+        requests::GenerateCertificateRequestBuilder,
+        responses::{
+            GenerateCertificateResponse, ReadCertificateResponse, RevokeCertificateResponse,
+        },
+    };
+    use crate::api::Secret;
+
+    blocking! {
+        fn generate(
+            mount: &str,
+            role: &str,
+            opts: Option<&mut GenerateCertificateRequestBuilder>,
+        ) -> GenerateCertificateResponse = crate::pki::cert::generate;
+        fn generate_with_lease(
+            mount: &str,
+            role: &str,
+            opts: Option<&mut GenerateCertificateRequestBuilder>,
+        ) -> Secret<GenerateCertificateResponse> = crate::pki::cert::generate_with_lease;
+        fn list(mount: &str) -> Vec<String> = crate::pki::cert::list;
+        fn read(mount: &str, serial: &str) -> ReadCertificateResponse = crate::pki::cert::read;
+        fn revoke(
+            mount: &str,
+            serial: &str,
+        ) -> RevokeCertificateResponse = crate::pki::cert::revoke;
+        fn tidy(mount: &str) -> () = crate::pki::cert::tidy;
+    }
+
+    pub mod ca {
+        use crate::api::pki::{
+            requests::{
+                GenerateRootRequestBuilder, SignCertificateRequestBuilder,
+                SignIntermediateRequestBuilder,
+            },
+            responses::{
+                GenerateRootResponse, SignCertificateResponse, SignIntermediateResponse,
+                SignSelfIssuedResponse,
+            },
+        };
+
+        blocking! {
+            fn delete(mount: &str) -> () = crate::pki::cert::ca::delete;
+            fn generate(
+                mount: &str,
+                cert_type: &str,
+                opts: Option<&mut GenerateRootRequestBuilder>,
+            ) -> Option<GenerateRootResponse> = crate::pki::cert::ca::generate;
+            fn sign(
+                mount: &str,
+                role: &str,
+                csr: &str,
+                common_name: &str,
+                opts: Option<&mut SignCertificateRequestBuilder>,
+            ) -> SignCertificateResponse = crate::pki::cert::ca::sign;
+            fn sign_intermediate(
+                mount: &str,
+                csr: &str,
+                common_name: &str,
+                opts: Option<&mut SignIntermediateRequestBuilder>,
+            ) -> SignIntermediateResponse = crate::pki::cert::ca::sign_intermediate;
+            fn sign_self_issued(
+                mount: &str,
+                certificate: &str,
+            ) -> SignSelfIssuedResponse = crate::pki::cert::ca::sign_self_issued;
+            fn submit(mount: &str, pem_bundle: &str) -> () = crate::pki::cert::ca::submit;
+        }
+
+        pub mod int {
+            use crate::api::pki::{
+                requests::{CrossSignRequestBuilder, GenerateIntermediateRequestBuilder},
+                responses::{
+                    CrossSignResponse, GenerateIntermediateResponse, ImportIssuerResponse,
+                },
+            };
+
+            blocking! {
+                fn generate(
+                    mount: &str,
+                    cert_type: &str,
+                    common_name: &str,
+                    opts: Option<&mut GenerateIntermediateRequestBuilder>,
+                ) -> GenerateIntermediateResponse = crate::pki::cert::ca::int::generate;
+                fn set_signed(
+                    mount: &str,
+                    certificate: &str,
+                ) -> ImportIssuerResponse = crate::pki::cert::ca::int::set_signed;
+                fn cross_sign(
+                    mount: &str,
+                    opts: Option<&mut CrossSignRequestBuilder>,
+                ) -> CrossSignResponse = crate::pki::cert::ca::int::cross_sign;
+            }
+        }
+    }
+
+    pub mod crl {
+        use crate::api::pki::{
+            requests::SetCRLConfigRequestBuilder,
+            responses::{ReadCRLConfigResponse, RotateCRLsResponse},
+        };
+
+        blocking! {
+            fn rotate(mount: &str) -> RotateCRLsResponse = crate::pki::cert::crl::rotate;
+            fn read_config(
+                mount: &str,
+            ) -> ReadCRLConfigResponse = crate::pki::cert::crl::read_config;
+            fn set_config(
+                mount: &str,
+                opts: Option<&mut SetCRLConfigRequestBuilder>,
+            ) -> () = crate::pki::cert::crl::set_config;
+        }
+    }
+
+    pub mod urls {
+        use crate::api::pki::{requests::SetURLsRequestBuilder, responses::ReadURLsResponse};
+
+        blocking! {
+            fn read(mount: &str) -> ReadURLsResponse = crate::pki::cert::urls::read;
+            fn set(
+                mount: &str,
+                opts: Option<&mut SetURLsRequestBuilder>,
+            ) -> () = crate::pki::cert::urls::set;
+        }
+    }
+}
+
+pub mod issuer {
+    use crate::api::pki::{
+        requests::{SignIntermediateIssuerRequestBuilder, UpdateIssuerRequestBuilder},
+        responses::{
+            ImportIssuerResponse, ListIssuersResponse, ReadIssuerCertificateResponse,
+            SetDefaultIssuerResponse, SignIntermediateIssuerResponse, UpdateIssuerResponse,
+        },
+    };
+
+    blocking! {
+        fn list(mount: &str) -> ListIssuersResponse = crate::pki::issuer::list;
+        fn read(
+            mount: &str,
+            issuer: Option<&str>,
+        ) -> ReadIssuerCertificateResponse = crate::pki::issuer::read;
+        fn sign_intermediate(
+            mount: &str,
+            csr: &str,
+            common_name: &str,
+            issuer: Option<&str>,
+            opts: Option<&mut SignIntermediateIssuerRequestBuilder>,
+        ) -> SignIntermediateIssuerResponse = crate::pki::issuer::sign_intermediate;
+        fn import(
+            mount: &str,
+            pem_bundle: &str,
+        ) -> ImportIssuerResponse = crate::pki::issuer::import;
+        fn set_default(
+            mount: &str,
+            default_issuer: &str,
+        ) -> SetDefaultIssuerResponse = crate::pki::issuer::set_default;
+        fn update(
+            mount: &str,
+            issuer_ref: &str,
+            opts: Option<&mut UpdateIssuerRequestBuilder>,
+        ) -> UpdateIssuerResponse = crate::pki::issuer::update;
+        fn delete(mount: &str, issuer: &str) -> () = crate::pki::issuer::delete;
+    }
+
+    pub mod int {
+        use crate::api::pki::{
+            requests::GenerateIntermediateCSRRequestBuilder,
+            responses::GenerateIntermediateCSRResponse,
+        };
+
+        blocking! {
+            fn generate(
+                mount: &str,
+                request_type: &str,
+                common_name: &str,
+                opts: Option<&mut GenerateIntermediateCSRRequestBuilder>,
+            ) -> GenerateIntermediateCSRResponse = crate::pki::issuer::int::generate;
+        }
+    }
+}
+
+pub mod role {
+    use crate::api::pki::{
+        requests::SetRoleRequestBuilder,
+        responses::{ListRolesResponse, ReadRoleResponse},
+    };
+
+    blocking! {
+        fn delete(mount: &str, name: &str) -> () = crate::pki::role::delete;
+        fn list(mount: &str) -> ListRolesResponse = crate::pki::role::list;
+        fn read(mount: &str, name: &str) -> ReadRoleResponse = crate::pki::role::read;
+        fn set(
+            mount: &str,
+            name: &str,
+            opts: Option<&mut SetRoleRequestBuilder>,
+        ) -> () = crate::pki::role::set;
+    }
+}
+
+pub mod key {
+    blocking! {
+        fn delete(mount: &str, key: &str) -> () = crate::pki::key::delete;
+    }
+}
@@ -0,0 +1,233 @@
+use crate::api::sys::{
+    requests::StartInitializationRequestBuilder,
+    responses::{ReadHealthResponse, StartInitializationResponse, UnsealResponse},
+};
+use crate::sys::ServerStatus;
+
+blocking! {
+    fn health() -> ReadHealthResponse = crate::sys::health;
+    fn start_initialization(
+        secret_shares: u64,
+        secret_threshold: u64,
+        opts: Option<&mut StartInitializationRequestBuilder>,
+    ) -> StartInitializationResponse = crate::sys::start_initialization;
+    fn seal() -> () = crate::sys::seal;
+    fn unseal(
+        key: Option<String>,
+        reset: Option<bool>,
+        migrate: Option<bool>,
+    ) -> UnsealResponse = crate::sys::unseal;
+    fn status() -> ServerStatus = crate::sys::status;
+}
+
+pub mod rekey {
+    use crate::api::sys::{
+        requests::StartRekeyRequestBuilder,
+        responses::{
+            RekeyBackupResponse, RekeyStatusResponse, RekeyUpdateResponse,
+            RekeyVerificationStatusResponse, RekeyVerifyResponse,
+        },
+    };
+
+    blocking! {
+        fn status() -> RekeyStatusResponse = crate::sys::rekey::status;
+        fn start(
+            secret_shares: u64,
+            secret_threshold: u64,
+            opts: Option<&mut StartRekeyRequestBuilder>,
+        ) -> RekeyStatusResponse = crate::sys::rekey::start;
+        fn cancel() -> () = crate::sys::rekey::cancel;
+        fn update(key: &str, nonce: &str) -> RekeyUpdateResponse = crate::sys::rekey::update;
+        fn read_backup() -> RekeyBackupResponse = crate::sys::rekey::read_backup;
+        fn delete_backup() -> () = crate::sys::rekey::delete_backup;
+        fn verification_status(
+        ) -> RekeyVerificationStatusResponse = crate::sys::rekey::verification_status;
+        fn verify(key: &str, nonce: &str) -> RekeyVerifyResponse = crate::sys::rekey::verify;
+        fn cancel_verification(
+        ) -> RekeyVerificationStatusResponse = crate::sys::rekey::cancel_verification;
+    }
+}
+
+pub mod generate_root {
+    use crate::api::sys::{
+        requests::StartGenerateRootRequestBuilder, responses::GenerateRootStatusResponse,
+    };
+
+    pub use crate::sys::generate_root::decode_token;
+
+    blocking! {
+        fn status() -> GenerateRootStatusResponse = crate::sys::generate_root::status;
+        fn start(
+            opts: Option<&mut StartGenerateRootRequestBuilder>,
+        ) -> GenerateRootStatusResponse = crate::sys::generate_root::start;
+        fn cancel() -> () = crate::sys::generate_root::cancel;
+        fn update(
+            key: &str,
+            nonce: &str,
+        ) -> GenerateRootStatusResponse = crate::sys::generate_root::update;
+    }
+}
+
+pub mod auth {
+    use std::collections::HashMap;
+
+    use crate::api::sys::{
+        requests::{EnableAuthRequestBuilder, TuneAuthRequestBuilder},
+        responses::{AuthResponse, MountTuneResponse},
+    };
+
+    blocking! {
+        fn enable(
+            path: &str,
+            engine_type: &str,
+            opts: Option<&mut EnableAuthRequestBuilder>,
+        ) -> () = crate::sys::auth::enable;
+        fn disable(path: &str) -> () = crate::sys::auth::disable;
+        fn list() -> HashMap<String, AuthResponse> = crate::sys::auth::list;
+        fn read_tune(path: &str) -> MountTuneResponse = crate::sys::auth::read_tune;
+        fn tune(
+            path: &str,
+            opts: Option<&mut TuneAuthRequestBuilder>,
+        ) -> () = crate::sys::auth::tune;
+    }
+}
+
+pub mod audit {
+    use std::collections::HashMap;
+
+    use crate::api::sys::{
+        requests::{AuditDevice, EnableAuditDeviceRequestBuilder},
+        responses::{AuditDeviceResponse, AuditHashResponse},
+    };
+
+    blocking! {
+        fn enable(
+            path: &str,
+            device: AuditDevice,
+            opts: Option<&mut EnableAuditDeviceRequestBuilder>,
+        ) -> () = crate::sys::audit::enable;
+        fn disable(path: &str) -> () = crate::sys::audit::disable;
+        fn list() -> HashMap<String, AuditDeviceResponse> = crate::sys::audit::list;
+        fn hash(path: &str, input: &str) -> AuditHashResponse = crate::sys::audit::hash;
+    }
+}
+
+pub mod mount {
+    use std::collections::HashMap;
+
+    use crate::api::sys::{
+        requests::{EnableEngineRequestBuilder, TuneMountRequestBuilder},
+        responses::{GetConfigurationOfTheSecretEngineResponse, MountResponse, MountTuneResponse},
+    };
+
+    blocking! {
+        fn enable(
+            path: &str,
+            engine_type: &str,
+            opts: Option<&mut EnableEngineRequestBuilder>,
+        ) -> () = crate::sys::mount::enable;
+        fn disable(path: &str) -> () = crate::sys::mount::disable;
+        fn get_configuration_of_a_secret_engine(
+            path: &str,
+        ) -> GetConfigurationOfTheSecretEngineResponse = crate::sys::mount::get_configuration_of_a_secret_engine;
+        fn list() -> HashMap<String, MountResponse> = crate::sys::mount::list;
+        fn read_tune(path: &str) -> MountTuneResponse = crate::sys::mount::read_tune;
+        fn tune(
+            path: &str,
+            opts: Option<&mut TuneMountRequestBuilder>,
+        ) -> () = crate::sys::mount::tune;
+    }
+}
+
+pub mod remount {
+    use crate::api::sys::responses::{RemountResponse, RemountStatusResponse};
+
+    blocking! {
+        fn remount(from: &str, to: &str) -> RemountResponse = crate::sys::remount::remount;
+        fn remount_status(
+            migration_id: &str,
+        ) -> RemountStatusResponse = crate::sys::remount::remount_status;
+    }
+}
+
+pub mod policy {
+    use crate::api::sys::responses::{ListPoliciesResponse, ReadPolicyResponse};
+
+    blocking! {
+        fn delete(name: &str) -> () = crate::sys::policy::delete;
+        fn list() -> ListPoliciesResponse = crate::sys::policy::list;
+        fn read(name: &str) -> ReadPolicyResponse = crate::sys::policy::read;
+        fn set(name: &str, policy: &str) -> () = crate::sys::policy::set;
+    }
+}
+
+pub mod capabilities {
+    use std::collections::HashMap;
+
+    use crate::api::sys::Capability;
+
+    blocking! {
+        fn token(
+            token: &str,
+            paths: &[&str],
+        ) -> HashMap<String, Vec<Capability>> = crate::sys::capabilities::token;
+        fn token_self(
+            paths: &[&str],
+        ) -> HashMap<String, Vec<Capability>> = crate::sys::capabilities::token_self;
+        fn accessor(
+            accessor: &str,
+            paths: &[&str],
+        ) -> HashMap<String, Vec<Capability>> = crate::sys::capabilities::accessor;
+    }
+}
+
+pub mod wrapping {
+    use serde::{de::DeserializeOwned, Serialize};
+
+    use crate::api::{sys::responses::WrappingLookupResponse, WrapInfo};
+
+    blocking! {
+        fn lookup(token: &str) -> WrappingLookupResponse = crate::sys::wrapping::lookup;
+        fn unwrap<D: DeserializeOwned>(token: Option<&str>) -> D = crate::sys::wrapping::unwrap;
+        fn wrap<T: Serialize>(data: &T, ttl: Option<&str>) -> WrapInfo = crate::sys::wrapping::wrap;
+        fn rewrap(token: &str) -> WrapInfo = crate::sys::wrapping::rewrap;
+    }
+}
+
+pub mod tools {
+    use crate::api::sys::{requests::RandomRequestBuilder, responses::RandomResponse};
+
+    blocking! {
+        fn random(
+            opts: Option<&mut RandomRequestBuilder>,
+        ) -> RandomResponse = crate::sys::tools::random;
+    }
+}
+
+pub mod lease {
+    use crate::api::sys::{
+        requests::{
+            RenewLeaseRequestBuilder, RevokeLeaseRequestBuilder, RevokePrefixRequestBuilder,
+        },
+        responses::{ListLeasesResponse, ReadLeaseResponse, RenewLeaseResponse},
+    };
+
+    blocking! {
+        fn list(prefix: &str) -> ListLeasesResponse = crate::sys::lease::list;
+        fn read(lease_id: &str) -> ReadLeaseResponse = crate::sys::lease::read;
+        fn renew(
+            lease_id: &str,
+            opts: Option<&mut RenewLeaseRequestBuilder>,
+        ) -> RenewLeaseResponse = crate::sys::lease::renew;
+        fn revoke(
+            lease_id: &str,
+            opts: Option<&mut RevokeLeaseRequestBuilder>,
+        ) -> () = crate::sys::lease::revoke;
+        fn revoke_prefix(
+            prefix: &str,
+            opts: Option<&mut RevokePrefixRequestBuilder>,
+        ) -> () = crate::sys::lease::revoke_prefix;
+        fn revoke_force(prefix: &str) -> () = crate::sys::lease::revoke_force;
+        fn tidy() -> () = crate::sys::lease::tidy;
+    }
+}
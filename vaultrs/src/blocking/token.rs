@@ -0,0 +1,52 @@
+use crate::api::{
+    token::{
+        requests::{
+            CreateOrphanTokenRequestBuilder, CreateRoleTokenRequestBuilder,
+            CreateTokenRequestBuilder,
+        },
+        responses::LookupTokenResponse,
+    },
+    AuthInfo,
+};
+
+blocking! {
+    fn lookup(token: &str) -> LookupTokenResponse = crate::token::lookup;
+    fn lookup_accessor(accessor: &str) -> LookupTokenResponse = crate::token::lookup_accessor;
+    fn lookup_self() -> LookupTokenResponse = crate::token::lookup_self;
+    fn new(opts: Option<&mut CreateTokenRequestBuilder>) -> AuthInfo = crate::token::new;
+    fn new_orphan(
+        opts: Option<&mut CreateOrphanTokenRequestBuilder>,
+    ) -> AuthInfo = crate::token::new_orphan;
+    fn new_role(
+        role: &str,
+        opts: Option<&mut CreateRoleTokenRequestBuilder>,
+    ) -> AuthInfo = crate::token::new_role;
+    fn renew(token: &str, increment: Option<&str>) -> AuthInfo = crate::token::renew;
+    fn renew_accessor(
+        accessor: &str,
+        increment: Option<&str>,
+    ) -> AuthInfo = crate::token::renew_accessor;
+    fn renew_self(increment: Option<&str>) -> AuthInfo = crate::token::renew_self;
+    fn revoke(token: &str) -> () = crate::token::revoke;
+    fn revoke_accessor(accessor: &str) -> () = crate::token::revoke_accessor;
+    fn revoke_orphan(token: &str) -> () = crate::token::revoke_orphan;
+    fn revoke_self() -> () = crate::token::revoke_self;
+    fn tidy() -> () = crate::token::tidy;
+}
+
+pub mod role {
+    use crate::api::token::{
+        requests::SetTokenRoleRequestBuilder,
+        responses::{ListTokenRolesResponse, ReadTokenRoleResponse},
+    };
+
+    blocking! {
+        fn delete(role_name: &str) -> () = crate::token::role::delete;
+        fn list() -> ListTokenRolesResponse = crate::token::role::list;
+        fn read(role_name: &str) -> ReadTokenRoleResponse = crate::token::role::read;
+        fn set(
+            role_name: &str,
+            opts: Option<&mut SetTokenRoleRequestBuilder>,
+        ) -> () = crate::token::role::set;
+    }
+}
@@ -0,0 +1,162 @@
+pub mod key {
+    use crate::api::transit::{
+        requests::{
+            CreateKeyRequestBuilder, ExportKeyType, ExportVersion, ImportKeyRequestBuilder,
+            ImportKeyVersionRequestBuilder, RestoreKeyRequestBuilder,
+            UpdateKeyConfigurationRequestBuilder,
+        },
+        responses::{BackupKeyResponse, ExportKeyResponse, ListKeysResponse, ReadKeyResponse},
+    };
+
+    blocking! {
+        fn create(
+            mount: &str,
+            name: &str,
+            opts: Option<&mut CreateKeyRequestBuilder>,
+        ) -> () = crate::transit::key::create;
+        fn read(mount: &str, name: &str) -> ReadKeyResponse = crate::transit::key::read;
+        fn list(mount: &str) -> ListKeysResponse = crate::transit::key::list;
+        fn update(
+            mount: &str,
+            name: &str,
+            opts: Option<&mut UpdateKeyConfigurationRequestBuilder>,
+        ) -> () = crate::transit::key::update;
+        fn delete(mount: &str, name: &str) -> () = crate::transit::key::delete;
+        fn rotate(mount: &str, name: &str) -> () = crate::transit::key::rotate;
+        fn import(
+            mount: &str,
+            name: &str,
+            opts: Option<&mut ImportKeyRequestBuilder>,
+        ) -> () = crate::transit::key::import;
+        fn import_version(
+            mount: &str,
+            name: &str,
+            opts: Option<&mut ImportKeyVersionRequestBuilder>,
+        ) -> () = crate::transit::key::import_version;
+        fn export(
+            mount: &str,
+            name: &str,
+            key_type: ExportKeyType,
+            version: ExportVersion,
+        ) -> ExportKeyResponse = crate::transit::key::export;
+        fn backup(mount: &str, name: &str) -> BackupKeyResponse = crate::transit::key::backup;
+        fn restore(
+            mount: &str,
+            backup: &str,
+            opts: Option<&mut RestoreKeyRequestBuilder>,
+        ) -> () = crate::transit::key::restore;
+        fn trim(
+            mount: &str,
+            name: &str,
+            min_available_version: u64,
+        ) -> () = crate::transit::key::trim;
+    }
+}
+
+pub mod data {
+    use crate::api::transit::{
+        requests::{
+            DecryptDataRequestBuilder, EncryptDataRequestBuilder, RewrapDataRequestBuilder,
+            SignDataRequestBuilder, VerifySignedDataRequestBuilder,
+        },
+        responses::{
+            DecryptDataResponse, EncryptDataResponse, RewrapDataResponse, SignDataResponse,
+            VerifySignedDataResponse,
+        },
+    };
+
+    blocking! {
+        fn encrypt(
+            mount: &str,
+            name: &str,
+            plaintext: &str,
+            opts: Option<&mut EncryptDataRequestBuilder>,
+        ) -> EncryptDataResponse = crate::transit::data::encrypt;
+        fn decrypt(
+            mount: &str,
+            name: &str,
+            ciphertext: &str,
+            opts: Option<&mut DecryptDataRequestBuilder>,
+        ) -> DecryptDataResponse = crate::transit::data::decrypt;
+        fn rewrap(
+            mount: &str,
+            name: &str,
+            ciphertext: &str,
+            opts: Option<&mut RewrapDataRequestBuilder>,
+        ) -> RewrapDataResponse = crate::transit::data::rewrap;
+        fn sign(
+            mount: &str,
+            name: &str,
+            input: &str,
+            opts: Option<&mut SignDataRequestBuilder>,
+        ) -> SignDataResponse = crate::transit::data::sign;
+        fn verify(
+            mount: &str,
+            name: &str,
+            input: &str,
+            opts: Option<&mut VerifySignedDataRequestBuilder>,
+        ) -> VerifySignedDataResponse = crate::transit::data::verify;
+    }
+}
+
+pub mod generate {
+    use crate::api::transit::{
+        requests::{
+            DataKeyType, GenerateDataKeyRequestBuilder, GenerateHmacRequestBuilder,
+            GenerateRandomBytesRequestBuilder, HashDataRequestBuilder, RandomBytesSource,
+        },
+        responses::{
+            GenerateDataKeyResponse, GenerateHmacResponse, GenerateRandomBytesResponse,
+            HashDataResponse,
+        },
+        OutputFormat,
+    };
+
+    blocking! {
+        fn data_key(
+            mount: &str,
+            name: &str,
+            key_type: DataKeyType,
+            opts: Option<&mut GenerateDataKeyRequestBuilder>,
+        ) -> GenerateDataKeyResponse = crate::transit::generate::data_key;
+        fn random_bytes(
+            mount: &str,
+            format: OutputFormat,
+            source: RandomBytesSource,
+            opts: Option<&mut GenerateRandomBytesRequestBuilder>,
+        ) -> GenerateRandomBytesResponse = crate::transit::generate::random_bytes;
+        fn hash(
+            mount: &str,
+            input: &str,
+            opts: Option<&mut HashDataRequestBuilder>,
+        ) -> HashDataResponse = crate::transit::generate::hash;
+        fn hmac(
+            mount: &str,
+            name: &str,
+            input: &str,
+            opts: Option<&mut GenerateHmacRequestBuilder>,
+        ) -> GenerateHmacResponse = crate::transit::generate::hmac;
+    }
+}
+
+pub mod cache {
+    use crate::api::transit::{
+        requests::ConfigureCacheRequestBuilder, responses::ReadTransitCacheConfigurationResponse,
+    };
+
+    blocking! {
+        fn read(mount: &str) -> ReadTransitCacheConfigurationResponse = crate::transit::cache::read;
+        fn configure(
+            mount: &str,
+            opts: Option<&mut ConfigureCacheRequestBuilder>,
+        ) -> () = crate::transit::cache::configure;
+    }
+}
+
+pub mod wrapping_key {
+    use crate::api::transit::responses::GetWrappingKeyResponse;
+
+    blocking! {
+        fn get(mount: &str) -> GetWrappingKeyResponse = crate::transit::wrapping_key::get;
+    }
+}
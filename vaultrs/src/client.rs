@@ -1,16 +1,39 @@
+pub mod consistency;
+pub mod failover;
+pub mod middleware;
+pub mod rate_limit;
+pub mod recording;
+pub mod retry;
+pub mod scoped;
+pub mod token_helper;
+
+use self::consistency::Consistency;
+use self::failover::Failover;
+use self::middleware::{Middleware, MiddlewareChain};
+use self::rate_limit::RateLimit;
+use self::recording::Recording;
+use self::retry::RetryPolicy;
+use self::scoped::ScopedClient;
+use self::token_helper::TokenHelper;
+use crate::api::sys::Capability;
 use crate::api::AuthInfo;
-use crate::api::{token::responses::LookupTokenResponse, EndpointMiddleware};
+use crate::api::{
+    token::responses::LookupTokenResponse, EndpointMiddleware, SecretString, SharedToken,
+};
 use crate::error::ClientError;
 use async_trait::async_trait;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
 pub use reqwest::Identity;
 use reqwest::Proxy;
 use rustify::clients::reqwest::Client as HTTPClient;
+use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::Duration;
 use std::{env, fs};
 use url::Url;
 
 /// Valid URL schemes that can be used for a Vault server address
-const VALID_SCHEMES: [&str; 2] = ["http", "https"];
+const VALID_SCHEMES: [&str; 3] = ["http", "https", "unix"];
 
 /// The client interface capabale of interacting with API functions
 #[async_trait]
@@ -27,6 +50,21 @@ pub trait Client: Send + Sync + Sized {
     /// Sets the underlying token for this client
     fn set_token(&mut self, token: &str);
 
+    /// Returns the token currently used by this client
+    fn token(&self) -> SecretString {
+        self.middle().token.get()
+    }
+
+    /// Replaces the token used by this client through a shared reference.
+    ///
+    /// Requests which are already in flight keep the token they were sent
+    /// with, every request sent afterwards uses the new one. Unlike
+    /// [Client::set_token] the token in the [settings][Client::settings] is
+    /// left unchanged.
+    fn swap_token(&self, token: &str) {
+        self.middle().token.set(token);
+    }
+
     /// Looks up the current token being used by this client
     async fn lookup(&self) -> Result<LookupTokenResponse, ClientError> {
         crate::token::lookup_self(self).await
@@ -46,6 +84,32 @@ pub trait Client: Send + Sync + Sized {
     async fn status(&self) -> Result<crate::sys::ServerStatus, ClientError> {
         crate::sys::status(self).await
     }
+
+    /// Returns the capabilities of the current token on the given path.
+    ///
+    /// Use [sys::capabilities][crate::sys::capabilities] to check several
+    /// paths, or other tokens, at once.
+    async fn capabilities(&self, path: &str) -> Result<Vec<Capability>, ClientError> {
+        let mut paths = crate::sys::capabilities::token_self(self, &[path]).await?;
+        Ok(paths.remove(path).unwrap_or_default())
+    }
+
+    /// Returns an interface for executing requests against arbitrary paths
+    fn logical(&self) -> crate::logical::Logical<'_, Self> {
+        crate::logical::Logical::new(self)
+    }
+
+    /// Returns a view of this client which can override the namespace, token,
+    /// wrap TTL and headers of the requests sent through it.
+    fn scoped(&self) -> ScopedClient<'_> {
+        ScopedClient::new(self)
+    }
+
+    /// Returns a view of this client sending its requests in the given
+    /// namespace.
+    fn with_namespace(&self, namespace: &str) -> ScopedClient<'_> {
+        self.scoped().namespace(namespace)
+    }
 }
 
 /// A client which can be used to execute calls against a Vault server.
@@ -74,8 +138,8 @@ impl Client for VaultClient {
     }
 
     fn set_token(&mut self, token: &str) {
-        self.settings.token = token.to_string();
-        self.middle.token = token.to_string();
+        self.settings.token = token.into();
+        self.middle.token.set(token);
     }
 }
 
@@ -106,7 +170,7 @@ impl VaultClient {
         http_client = http_client.tls_danger_accept_invalid_certs(!settings.verify);
 
         // Workaround https://github.com/seanmonstar/reqwest/issues/2988
-        if settings.address.as_str().starts_with("http://") {
+        if matches!(settings.address.scheme(), "http" | "unix") {
             http_client = http_client.tls_certs_only(Vec::new());
         } else {
             // Adds CA certificates
@@ -133,16 +197,84 @@ impl VaultClient {
             http_client = http_client.identity(identity.clone());
         }
 
+        // Connect to unix socket addresses through the socket, the URL of the
+        // requests only matters for their Host header
+        let mut address = settings.address.clone();
+        if address.scheme() == "unix" {
+            #[cfg(unix)]
+            {
+                debug!("Connecting through unix socket {}", address.path());
+                http_client = http_client.unix_socket(address.path());
+                address = Url::parse("http://localhost").unwrap();
+            }
+        }
+
+        // Verify the server certificate against a different name than the one
+        // of the address by sending the requests to that name, which resolves
+        // to the host of the address, while keeping the Host header unchanged
+        if let Some(name) = &settings.tls_server_name {
+            let host = address.host_str().unwrap_or_default().to_string();
+            let invalid = |reason: &str| ClientError::InvalidTlsServerName {
+                name: name.clone(),
+                host: settings.address.to_string(),
+                reason: reason.to_string(),
+            };
+            if settings.address.scheme() == "unix" {
+                return Err(invalid("unix socket addresses don't use TLS"));
+            }
+            if settings.failover.is_some() {
+                return Err(invalid("the name can't be verified for several addresses"));
+            }
+            // The name is resolved by the client, a proxy would resolve it
+            // itself
+            let proxy_vars = match address.scheme() {
+                "https" => ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"],
+                _ => ["HTTP_PROXY", "http_proxy", "ALL_PROXY", "all_proxy"],
+            };
+            if settings.proxy.is_some() || proxy_vars.iter().any(|v| env::var_os(v).is_some()) {
+                return Err(invalid("requests can't be sent through a proxy"));
+            }
+            let authority = match address.port() {
+                Some(port) => format!("{host}:{port}"),
+                None => host.clone(),
+            };
+            address
+                .set_host(Some(name))
+                .map_err(|_| invalid("the name isn't a valid host"))?;
+            debug!("Using TLS server name {} for {}", name, host);
+            let mut headers = reqwest::header::HeaderMap::new();
+            headers.insert(
+                reqwest::header::HOST,
+                reqwest::header::HeaderValue::from_str(&authority)
+                    .map_err(|_| invalid("the address isn't a valid host"))?,
+            );
+            http_client =
+                http_client
+                    .default_headers(headers)
+                    .dns_resolver(Arc::new(ServerNameResolver {
+                        name: name.to_ascii_lowercase(),
+                        host,
+                    }));
+        }
+
         // Configures middleware for endpoints to append API version and token
         debug!("Using API version {}", settings.version);
         let version_str = format!("v{}", settings.version);
         let middle = EndpointMiddleware {
-            token: settings.token.clone(),
+            token: SharedToken::new(&settings.token),
             version: version_str,
             wrap: None,
             namespace: settings.namespace.clone(),
+            chain: settings.middlewares.clone(),
+            consistency: settings.consistency.clone(),
         };
 
+        // Redirects from standby nodes are followed by the failover, which
+        // keeps track of the active node
+        if settings.failover.is_some() {
+            http_client = http_client.redirect(reqwest::redirect::Policy::none());
+        }
+
         if let Some(proxy_url) = &settings.proxy {
             http_client = http_client.proxy(
                 Proxy::all(proxy_url.as_str())
@@ -153,7 +285,7 @@ impl VaultClient {
         let http_client = http_client
             .build()
             .map_err(|e| ClientError::RestClientBuildError { source: e })?;
-        let http = HTTPClient::new(settings.address.as_str(), http_client);
+        let http = HTTPClient::new(address.as_str(), http_client);
         Ok(VaultClient {
             settings,
             middle,
@@ -167,13 +299,33 @@ impl VaultClient {
 /// Most settings that are not directly configured will have their default value
 /// pulled from their respective environment variables. Specifically:
 ///
-/// * `address`: VAULT_ADDR
+/// * `address`: VAULT_AGENT_ADDR / VAULT_ADDR
 /// * `ca_certs: VAULT_CACERT / VAULT_CAPATH
+/// * `identity`: VAULT_CLIENT_CERT / VAULT_CLIENT_KEY
+/// * `timeout`: VAULT_CLIENT_TIMEOUT
 /// * `token`: VAULT_TOKEN
 /// * verify`: VAULT_SKIP_VERIFY
+/// * `tls_server_name`: VAULT_TLS_SERVER_NAME
+/// * `wrap_ttl`: VAULT_WRAP_TTL
+/// * `namespace`: VAULT_NAMESPACE
+/// * `proxy`: VAULT_PROXY_ADDR / VAULT_HTTP_PROXY
+/// * `retry`: VAULT_MAX_RETRIES
+/// * `rate_limit`: VAULT_RATE_LIMIT
 ///
 /// The `address` is validated when the settings are built and will throw an
 /// error if the format is invalid.
+///
+/// The `tls_server_name` is the name the server certificate is verified
+/// against, while requests are still sent to the host of the `address`. It
+/// can't be used together with a unix socket address, a `failover` or a proxy
+/// (including the `HTTPS_PROXY`, `HTTP_PROXY` and `ALL_PROXY` environment
+/// variables), creating the client fails with
+/// [ClientError::InvalidTlsServerName] in that case.
+///
+/// Unlike the Vault CLI and Go client, which wrap every response once
+/// `VAULT_WRAP_TTL` is set, the `wrap_ttl` is only the TTL of the responses
+/// wrapped explicitly with
+/// [ResponseWrapper::wrap][crate::api::ResponseWrapper::wrap].
 #[derive(Builder, Clone, Debug)]
 #[builder(build_fn(validate = "Self::validate"))]
 pub struct VaultClientSettings {
@@ -183,20 +335,37 @@ pub struct VaultClientSettings {
     pub ca_certs: Vec<String>,
     #[builder(default = "self.default_identity()")]
     pub identity: Option<Identity>,
-    #[builder(default)]
+    #[builder(default = "self.default_timeout()")]
     pub timeout: Option<Duration>,
     #[builder(setter(into), default = "self.default_token()")]
-    pub token: String,
+    pub token: SecretString,
     #[builder(default = "self.default_verify()")]
     pub verify: bool,
     #[builder(setter(into, strip_option), default = "1")]
     pub version: u8,
-    #[builder(default = "false")]
-    pub wrapping: bool,
-    #[builder(default)]
+    /// The TTL of responses wrapped with
+    /// [ResponseWrapper::wrap][crate::api::ResponseWrapper::wrap]. Other
+    /// responses aren't wrapped, even when `VAULT_WRAP_TTL` is set.
+    #[builder(setter(into), default = "self.default_wrap_ttl()")]
+    pub wrap_ttl: String,
+    #[builder(default = "self.default_namespace()")]
     pub namespace: Option<String>,
-    #[builder(setter(custom), default)]
+    #[builder(setter(custom), default = "self.default_proxy()?")]
     pub proxy: Option<Url>,
+    #[builder(default = "self.default_retry()")]
+    pub retry: RetryPolicy,
+    #[builder(setter(into, strip_option), default = "self.default_tls_server_name()")]
+    pub tls_server_name: Option<String>,
+    #[builder(setter(strip_option), default = "self.default_rate_limit()")]
+    pub rate_limit: Option<RateLimit>,
+    #[builder(setter(strip_option), default)]
+    pub recording: Option<Recording>,
+    #[builder(setter(custom), default)]
+    pub middlewares: MiddlewareChain,
+    #[builder(setter(strip_option), default)]
+    pub failover: Option<Failover>,
+    #[builder(setter(strip_option), default)]
+    pub consistency: Option<Consistency>,
 }
 
 impl VaultClientSettingsBuilder {
@@ -204,6 +373,10 @@ impl VaultClientSettingsBuilder {
     /// to the `VAULT_ADDR` environment variable and if that is not set either,
     /// it will default to `http://127.0.0.1:8200`.
     ///
+    /// The address of a unix socket, like the listener of a Vault Agent or
+    /// Proxy, is given with the `unix` scheme and the absolute path of the
+    /// socket, e.g. `unix:///run/vault/agent.sock`.
+    ///
     /// # Panics
     ///
     /// The setter will panic if the address given contains an invalid URL format.
@@ -218,6 +391,32 @@ impl VaultClientSettingsBuilder {
         self
     }
 
+    /// Set the addresses of the nodes of a cluster, failing over between them.
+    /// The first address is used as the address of the client.
+    ///
+    /// See [Failover] to configure reads from performance standbys.
+    ///
+    /// # Panics
+    ///
+    /// The setter will panic if no address is given or an address contains an
+    /// invalid URL format.
+    pub fn addresses<T>(&mut self, addresses: impl IntoIterator<Item = T>) -> &mut Self
+    where
+        T: AsRef<str>,
+    {
+        let urls: Vec<Url> = addresses
+            .into_iter()
+            .map(|address| {
+                Url::parse(address.as_ref())
+                    .map_err(|_| format!("Invalid URL format: {}", address.as_ref()))
+                    .unwrap()
+            })
+            .collect();
+        self.address = Some(urls.first().expect("No address given").clone());
+        self.failover = Some(Some(Failover::new(urls)));
+        self
+    }
+
     /// Set an proxy address for vault.
     ///
     /// # Panics
@@ -234,13 +433,25 @@ impl VaultClientSettingsBuilder {
         self
     }
 
+    /// Appends a [Middleware] which is run for every request sent by the
+    /// client. Middlewares run in the order they are added.
+    pub fn middleware(&mut self, middleware: impl Middleware + 'static) -> &mut Self {
+        self.middlewares
+            .get_or_insert_with(MiddlewareChain::default)
+            .push(middleware);
+        self
+    }
+
     pub fn set_namespace(&mut self, str: String) -> &mut Self {
         self.namespace = Some(Some(str));
         self
     }
 
     fn default_address(&self) -> Result<Url, String> {
-        let address = if let Ok(address) = env::var("VAULT_ADDR") {
+        let address = if let Ok(address) = env::var("VAULT_AGENT_ADDR") {
+            debug!("Using vault agent address from $VAULT_AGENT_ADDR: {address}");
+            address
+        } else if let Ok(address) = env::var("VAULT_ADDR") {
             debug!("Using vault address from $VAULT_ADDR: {address}");
             address
         } else {
@@ -255,15 +466,30 @@ impl VaultClientSettingsBuilder {
         Ok(url)
     }
 
-    fn default_token(&self) -> String {
+    /// Set the token to the one returned by the given [TokenHelper]. The
+    /// token is left unset if the helper doesn't return one.
+    ///
+    /// Use a [TokenHelperChain::default] to pick up the token like the Vault
+    /// CLI does. Note that the helper is run right away, which may spawn an
+    /// external program and block the current thread.
+    ///
+    /// [TokenHelperChain::default]: crate::client::token_helper::TokenHelperChain::default
+    pub fn token_helper(&mut self, helper: &impl TokenHelper) -> Result<&mut Self, ClientError> {
+        if let Some(token) = helper.get()? {
+            self.token = Some(token);
+        }
+        Ok(self)
+    }
+
+    fn default_token(&self) -> SecretString {
         match env::var("VAULT_TOKEN") {
             Ok(s) => {
                 debug!("Using vault token from $VAULT_TOKEN");
-                s
+                s.into()
             }
             Err(_) => {
                 debug!("Using default empty vault token");
-                String::from("")
+                SecretString::default()
             }
         }
     }
@@ -276,6 +502,80 @@ impl VaultClientSettingsBuilder {
         }
     }
 
+    fn default_timeout(&self) -> Option<Duration> {
+        let s = env::var("VAULT_CLIENT_TIMEOUT").ok()?;
+        match parse_duration(&s) {
+            Some(timeout) => {
+                debug!("Using client timeout from $VAULT_CLIENT_TIMEOUT: {s}");
+                Some(timeout)
+            }
+            None => {
+                warn!("Ignoring invalid $VAULT_CLIENT_TIMEOUT: {s}");
+                None
+            }
+        }
+    }
+
+    fn default_tls_server_name(&self) -> Option<String> {
+        let name = env::var("VAULT_TLS_SERVER_NAME").ok()?;
+        debug!("Using TLS server name from $VAULT_TLS_SERVER_NAME: {name}");
+        Some(name)
+    }
+
+    fn default_wrap_ttl(&self) -> String {
+        match env::var("VAULT_WRAP_TTL") {
+            Ok(ttl) => {
+                debug!("Using wrap TTL from $VAULT_WRAP_TTL: {ttl}");
+                ttl
+            }
+            Err(_) => String::from("10m"),
+        }
+    }
+
+    fn default_namespace(&self) -> Option<String> {
+        let namespace = env::var("VAULT_NAMESPACE").ok()?;
+        debug!("Using namespace from $VAULT_NAMESPACE: {namespace}");
+        Some(namespace)
+    }
+
+    fn default_proxy(&self) -> Result<Option<Url>, String> {
+        let proxy = match env::var("VAULT_PROXY_ADDR") {
+            Ok(proxy) => proxy,
+            Err(_) => match env::var("VAULT_HTTP_PROXY") {
+                Ok(proxy) => proxy,
+                Err(_) => return Ok(None),
+            },
+        };
+        debug!("Using proxy address from environment: {proxy}");
+        Url::parse(&proxy)
+            .map(Some)
+            .map_err(|_| format!("Invalid URL format: {}", &proxy))
+    }
+
+    fn default_rate_limit(&self) -> Option<RateLimit> {
+        let s = env::var("VAULT_RATE_LIMIT").ok()?;
+        let limit = RateLimit::parse(&s);
+        match &limit {
+            Some(_) => debug!("Using rate limit from $VAULT_RATE_LIMIT: {s}"),
+            None => warn!("Ignoring invalid $VAULT_RATE_LIMIT: {s}"),
+        }
+        limit
+    }
+
+    fn default_retry(&self) -> RetryPolicy {
+        let mut policy = RetryPolicy::default();
+        if let Ok(s) = env::var("VAULT_MAX_RETRIES") {
+            match s.parse() {
+                Ok(retries) => {
+                    debug!("Using max retries from $VAULT_MAX_RETRIES: {retries}");
+                    policy.max_retries = retries;
+                }
+                Err(_) => warn!("Ignoring invalid $VAULT_MAX_RETRIES: {s}"),
+            }
+        }
+        policy
+    }
+
     fn default_ca_certs(&self) -> Vec<String> {
         let mut paths: Vec<String> = Vec::new();
 
@@ -354,8 +654,71 @@ impl VaultClientSettingsBuilder {
         // Verify scheme is valid HTTP endpoint
         if !VALID_SCHEMES.contains(&url.scheme()) {
             Err(format!("Invalid scheme for HTTP URL: {}", url.scheme()))
+        } else if cfg!(not(unix)) && url.scheme() == "unix" {
+            Err(String::from(
+                "Unix sockets are not supported on this platform",
+            ))
         } else {
             Ok(())
         }
     }
 }
+
+/// Resolves the TLS server name of a client to the host of its address, any
+/// other name is resolved as usual.
+struct ServerNameResolver {
+    name: String,
+    host: String,
+}
+
+impl Resolve for ServerNameResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = if name.as_str() == self.name {
+            self.host.clone()
+        } else {
+            name.as_str().to_string()
+        };
+        Box::pin(async move {
+            // The port is replaced by the one of the requested URL
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Parses a duration using the formats accepted by the Vault CLI: a number of
+/// seconds or a sequence of numbers followed by one of the `ms`, `s`, `m` or
+/// `h` units, like `1m30s`.
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if let Ok(secs) = value.parse::<f64>() {
+        return Duration::try_from_secs_f64(secs).ok();
+    }
+
+    let mut rest = value;
+    let mut secs = 0.0;
+    while !rest.is_empty() {
+        let split = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        let (number, tail) = rest.split_at(split);
+        let number: f64 = number.parse().ok()?;
+        let split = tail
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(split);
+        secs += match unit {
+            "s" => number,
+            "ms" => number / 1000.0,
+            "m" => number * 60.0,
+            "h" => number * 3600.0,
+            _ => return None,
+        };
+        rest = tail;
+    }
+    Duration::try_from_secs_f64(secs).ok()
+}
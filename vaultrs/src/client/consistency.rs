@@ -0,0 +1,171 @@
+use std::sync::{Arc, Mutex};
+
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+use base64::Engine;
+use http::{HeaderValue, Request, Response};
+
+/// The header returned by the server with the state of its storage after a
+/// write, and sent back to require that state.
+pub const INDEX_HEADER: &str = "X-Vault-Index";
+
+/// The header telling the server how to handle a request requiring a state it
+/// hasn't reached yet.
+pub const INCONSISTENT_HEADER: &str = "X-Vault-Inconsistent";
+
+/// How a node handles a request requiring a state it hasn't reached yet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InconsistentPolicy {
+    /// The request is forwarded to the active node.
+    ForwardActiveNode,
+    /// The request fails with a `412`, which is retried according to the
+    /// [RetryPolicy][crate::client::retry::RetryPolicy] of the client.
+    Fail,
+}
+
+impl InconsistentPolicy {
+    fn header(&self) -> &'static str {
+        match self {
+            InconsistentPolicy::ForwardActiveNode => "forward-active-node",
+            InconsistentPolicy::Fail => "fail",
+        }
+    }
+}
+
+/// Tracks the `X-Vault-Index` states returned by the server and requires them
+/// on later requests, so that a read sent to a performance standby or a
+/// replica sees the writes made before it.
+///
+/// States from the same cluster are merged, keeping only the most recent one.
+/// The tracked states are shared between clones, so every client configured
+/// with the same settings shares them.
+///
+/// ```
+/// use vaultrs::client::consistency::{Consistency, InconsistentPolicy};
+/// use vaultrs::client::VaultClientSettingsBuilder;
+///
+/// let settings = VaultClientSettingsBuilder::default()
+///     .address("https://127.0.0.1:8200")
+///     .consistency(Consistency::new().inconsistent(InconsistentPolicy::ForwardActiveNode))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Consistency {
+    policy: Option<InconsistentPolicy>,
+    states: Arc<Mutex<Vec<String>>>,
+}
+
+impl Consistency {
+    /// Creates a tracker without any state.
+    pub fn new() -> Self {
+        Consistency::default()
+    }
+
+    /// Sets how nodes handle requests requiring a state they haven't reached
+    /// yet. Nodes wait for the state to be reached if this isn't set.
+    pub fn inconsistent(mut self, policy: InconsistentPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Returns the tracked states.
+    pub fn states(&self) -> Vec<String> {
+        self.states.lock().unwrap().clone()
+    }
+
+    /// Tracks the given state, replacing any older state from the same
+    /// cluster.
+    pub fn record(&self, state: &str) {
+        let mut states = self.states.lock().unwrap();
+        let new = match State::parse(state) {
+            Some(new) => new,
+            None => {
+                if !states.iter().any(|s| s == state) {
+                    states.push(state.to_string());
+                }
+                return;
+            }
+        };
+
+        let mut merged = false;
+        for existing in states.iter_mut() {
+            let Some(old) = State::parse(existing) else {
+                continue;
+            };
+            if old.cluster_id == new.cluster_id {
+                if new.local_index >= old.local_index
+                    && new.replicated_index >= old.replicated_index
+                {
+                    *existing = state.to_string();
+                }
+                merged = true;
+            }
+        }
+        if !merged {
+            states.push(state.to_string());
+        }
+    }
+
+    /// Forgets every tracked state.
+    pub fn clear(&self) {
+        self.states.lock().unwrap().clear();
+    }
+
+    /// Adds the tracked states and the inconsistency policy to the request.
+    pub(crate) fn request(&self, req: &mut Request<Vec<u8>>) {
+        for state in self.states.lock().unwrap().iter() {
+            if let Ok(value) = HeaderValue::from_str(state) {
+                req.headers_mut().append(INDEX_HEADER, value);
+            }
+        }
+        if let Some(policy) = &self.policy {
+            req.headers_mut().insert(
+                INCONSISTENT_HEADER,
+                HeaderValue::from_static(policy.header()),
+            );
+        }
+    }
+
+    /// Tracks the state returned with the response, if any.
+    pub(crate) fn response(&self, resp: &Response<Vec<u8>>) {
+        if let Some(state) = resp
+            .headers()
+            .get(INDEX_HEADER)
+            .and_then(|s| s.to_str().ok())
+        {
+            trace!("Tracking storage state {}", state);
+            self.record(state);
+        }
+    }
+}
+
+/// The decoded form of a state, `v1:<cluster id>:<local index>:<replicated
+/// index>:<hmac>` encoded in base64.
+struct State {
+    cluster_id: String,
+    local_index: u64,
+    replicated_index: u64,
+}
+
+impl State {
+    fn parse(raw: &str) -> Option<Self> {
+        let decoded = String::from_utf8(decode_base64(raw)?).ok()?;
+        let parts: Vec<&str> = decoded.split(':').collect();
+        match parts.as_slice() {
+            ["v1", cluster_id, local, replicated, _] => Some(State {
+                cluster_id: cluster_id.to_string(),
+                local_index: local.parse().ok()?,
+                replicated_index: replicated.parse().ok()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes the base64 encoded state, which may be padded or not and use the
+/// standard or URL-safe alphabet.
+fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD]
+        .iter()
+        .find_map(|engine| engine.decode(input).ok())
+}
@@ -0,0 +1,297 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use http::{Request, Response, Uri};
+use rustify::client::Client as RustifyClient;
+use rustify::clients::reqwest::Client as HTTPClient;
+use rustify::errors::ClientError as RestClientError;
+use url::Url;
+
+/// The maximum number of redirects followed for a single request.
+const MAX_REDIRECTS: usize = 5;
+
+/// The time a node has to answer a health check by default.
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The state of a node of a cluster as reported by its
+/// [health](https://developer.hashicorp.com/vault/api-docs/system/health)
+/// endpoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeStatus {
+    /// The node is active and handles every request.
+    Active,
+    /// The node is a standby which redirects requests to the active node.
+    Standby,
+    /// The node is a performance standby which can serve reads itself.
+    PerformanceStandby,
+    /// The node is sealed.
+    Sealed,
+    /// The node isn't initialized.
+    Uninitialized,
+    /// The node couldn't be reached.
+    Unreachable,
+    /// The node hasn't been checked yet.
+    Unknown,
+}
+
+impl NodeStatus {
+    /// Returns the status matching the code returned by the health endpoint
+    /// with its default parameters.
+    pub fn from_health_code(code: u16) -> Self {
+        match code {
+            200 => NodeStatus::Active,
+            429 | 472 => NodeStatus::Standby,
+            473 => NodeStatus::PerformanceStandby,
+            501 => NodeStatus::Uninitialized,
+            503 => NodeStatus::Sealed,
+            _ => NodeStatus::Unknown,
+        }
+    }
+
+    /// Returns whether the node can serve requests.
+    pub fn is_available(&self) -> bool {
+        !matches!(
+            self,
+            NodeStatus::Sealed | NodeStatus::Uninitialized | NodeStatus::Unreachable
+        )
+    }
+}
+
+/// Sends requests to the nodes of a cluster, failing over to another node
+/// when one can't be reached or is sealed.
+///
+/// The health of every node is checked before the first request and again
+/// whenever no node could serve a request. Requests are sent to the active
+/// node when it's known and redirects from standby nodes are followed,
+/// updating the active node. Reads can be routed to performance standbys with
+/// [Failover::read_from_standby].
+///
+/// Writes are only sent to another node when the connection to a node
+/// couldn't be established, since a node which failed while handling a write
+/// may already have applied it.
+///
+/// The state of the nodes is shared between clones.
+#[derive(Clone, Debug)]
+pub struct Failover {
+    addresses: Vec<Url>,
+    read_from_standby: bool,
+    health_timeout: Duration,
+    nodes: Arc<Mutex<Vec<NodeStatus>>>,
+}
+
+impl Failover {
+    /// Creates a failover between the given addresses, which are tried in
+    /// order while the health of the nodes isn't known.
+    pub fn new(addresses: Vec<Url>) -> Self {
+        let nodes = vec![NodeStatus::Unknown; addresses.len()];
+        Failover {
+            addresses,
+            read_from_standby: false,
+            health_timeout: HEALTH_TIMEOUT,
+            nodes: Arc::new(Mutex::new(nodes)),
+        }
+    }
+
+    /// Sets whether reads (`GET` and `LIST` requests) can be sent to
+    /// performance standby nodes.
+    pub fn read_from_standby(mut self, allowed: bool) -> Self {
+        self.read_from_standby = allowed;
+        self
+    }
+
+    /// Sets the time a node has to answer a health check before it's
+    /// considered unreachable. Defaults to 5 seconds.
+    pub fn health_timeout(mut self, timeout: Duration) -> Self {
+        self.health_timeout = timeout;
+        self
+    }
+
+    /// Returns the addresses of the nodes.
+    pub fn addresses(&self) -> &[Url] {
+        &self.addresses
+    }
+
+    /// Returns the last known status of every node.
+    pub fn status(&self) -> Vec<(Url, NodeStatus)> {
+        let nodes = self.nodes.lock().unwrap();
+        self.addresses
+            .iter()
+            .cloned()
+            .zip(nodes.iter().copied())
+            .collect()
+    }
+
+    /// Checks the health of every node.
+    pub async fn refresh(&self, http: &reqwest::Client) {
+        for (i, address) in self.addresses.iter().enumerate() {
+            let status = match address.join("v1/sys/health") {
+                Ok(url) => match http.get(url).timeout(self.health_timeout).send().await {
+                    Ok(response) => NodeStatus::from_health_code(response.status().as_u16()),
+                    Err(_) => NodeStatus::Unreachable,
+                },
+                Err(_) => NodeStatus::Unreachable,
+            };
+            debug!("Node {} is {:?}", address, status);
+            self.set(i, status);
+        }
+    }
+
+    fn set(&self, node: usize, status: NodeStatus) {
+        self.nodes.lock().unwrap()[node] = status;
+    }
+
+    /// Returns the nodes to try for a request in order of preference.
+    fn candidates(&self, read: bool) -> Vec<usize> {
+        let nodes = self.nodes.lock().unwrap();
+        let rank = |status: &NodeStatus| match status {
+            NodeStatus::PerformanceStandby if read && self.read_from_standby => 0,
+            NodeStatus::Active => 1,
+            NodeStatus::Unknown => 2,
+            NodeStatus::PerformanceStandby | NodeStatus::Standby => 3,
+            _ => 4,
+        };
+        let mut candidates: Vec<usize> = (0..nodes.len()).collect();
+        candidates.sort_by_key(|i| rank(&nodes[*i]));
+        candidates
+    }
+
+    fn node_of(&self, uri: &Uri) -> Option<usize> {
+        let url = Url::parse(&uri.to_string()).ok()?;
+        self.addresses
+            .iter()
+            .position(|a| a.origin() == url.origin())
+    }
+
+    /// Sends the request to the given node, following the redirects of
+    /// standby nodes.
+    async fn send_to(
+        &self,
+        http: &HTTPClient,
+        req: &Request<Vec<u8>>,
+        node: usize,
+    ) -> Result<Response<Vec<u8>>, RestClientError> {
+        let mut uri = rebase(req.uri(), &self.addresses[node]);
+        let mut current = Some(node);
+        for _ in 0..MAX_REDIRECTS {
+            let response = http.send(copy(req, uri.clone())).await?;
+            if !matches!(response.status().as_u16(), 307 | 308) {
+                return Ok(response);
+            }
+            let location = response
+                .headers()
+                .get(http::header::LOCATION)
+                .and_then(|l| l.to_str().ok())
+                .and_then(|l| l.parse::<Uri>().ok());
+            let Some(location) = location else {
+                return Ok(response);
+            };
+            debug!("Following redirect from {} to {}", uri, location);
+
+            // The node redirecting is a standby and the target is active
+            if let Some(from) = current {
+                self.set(from, NodeStatus::Standby);
+            }
+            current = self.node_of(&location);
+            if let Some(to) = current {
+                self.set(to, NodeStatus::Active);
+            }
+            uri = location;
+        }
+        http.send(copy(req, uri)).await
+    }
+}
+
+/// Sends requests through an [HTTPClient], failing over between the nodes of
+/// the [Failover] configured in the settings of a client.
+pub(crate) struct FailoverClient<'a> {
+    http: &'a HTTPClient,
+    failover: Option<&'a Failover>,
+}
+
+impl<'a> FailoverClient<'a> {
+    pub(crate) fn new(http: &'a HTTPClient, failover: Option<&'a Failover>) -> Self {
+        FailoverClient { http, failover }
+    }
+}
+
+#[async_trait]
+impl RustifyClient for FailoverClient<'_> {
+    async fn send(&self, req: Request<Vec<u8>>) -> Result<Response<Vec<u8>>, RestClientError> {
+        let failover = match self.failover {
+            Some(failover) => failover,
+            None => return self.http.send(req).await,
+        };
+
+        let read = matches!(req.method().as_str(), "GET" | "LIST" | "HEAD");
+        let unknown = failover
+            .nodes
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|n| *n == NodeStatus::Unknown);
+        if unknown {
+            failover.refresh(&self.http.http).await;
+        }
+
+        let mut refreshed = unknown;
+        let mut last = None;
+        loop {
+            for node in failover.candidates(read) {
+                let result = failover.send_to(self.http, &req, node).await;
+                match &result {
+                    Err(RestClientError::RequestError { source, .. }) => {
+                        warn!("Node {} is unreachable", failover.addresses[node]);
+                        failover.set(node, NodeStatus::Unreachable);
+                        // The request reached the node unless connecting
+                        // failed, so a write may already have been applied
+                        let connect = source
+                            .downcast_ref::<reqwest::Error>()
+                            .is_some_and(reqwest::Error::is_connect);
+                        if !read && !connect {
+                            return result;
+                        }
+                    }
+                    Ok(response) if response.status().as_u16() == 503 => {
+                        warn!("Node {} is unavailable", failover.addresses[node]);
+                        failover.set(node, NodeStatus::Sealed);
+                    }
+                    _ => return result,
+                }
+                last = Some(result);
+            }
+            if refreshed {
+                break;
+            }
+            failover.refresh(&self.http.http).await;
+            refreshed = true;
+        }
+
+        match last {
+            Some(result) => result,
+            None => self.http.send(req).await,
+        }
+    }
+
+    fn base(&self) -> &str {
+        self.http.base()
+    }
+}
+
+/// Replaces the scheme and authority of the URI with the ones of the address.
+fn rebase(uri: &Uri, address: &Url) -> Uri {
+    let path = uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+    let origin = address.origin().ascii_serialization();
+    format!("{origin}{path}")
+        .parse()
+        .unwrap_or_else(|_| uri.clone())
+}
+
+fn copy(req: &Request<Vec<u8>>, uri: Uri) -> Request<Vec<u8>> {
+    let mut copy = Request::new(req.body().clone());
+    *copy.method_mut() = req.method().clone();
+    *copy.uri_mut() = uri;
+    *copy.version_mut() = req.version();
+    *copy.headers_mut() = req.headers().clone();
+    copy
+}
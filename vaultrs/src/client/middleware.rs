@@ -0,0 +1,143 @@
+use std::fmt;
+use std::sync::Arc;
+
+use http::header::{HeaderName, HeaderValue};
+use http::{HeaderMap, Request, Response};
+
+use crate::error::ClientError;
+
+/// A hook which is run for every request sent by a client and every
+/// successful response it receives.
+///
+/// Middlewares are added to a client through
+/// [VaultClientSettingsBuilder::middleware][crate::client::VaultClientSettingsBuilder::middleware]
+/// and run in the order they were added, after the client has added its own
+/// headers (token, namespace, ...) so that they see the final request. Their
+/// response hooks run in the reverse order. Returning an error aborts the
+/// request with that error.
+///
+/// Closures taking a request are middlewares which only modify requests:
+///
+/// ```
+/// use vaultrs::client::VaultClientSettingsBuilder;
+/// use vaultrs::error::ClientError;
+///
+/// let settings = VaultClientSettingsBuilder::default()
+///     .address("https://127.0.0.1:8200")
+///     .middleware(|req: &mut http::Request<Vec<u8>>| -> Result<(), ClientError> {
+///         req.headers_mut()
+///             .insert("X-Request-Id", http::HeaderValue::from_static("1234"));
+///         Ok(())
+///     })
+///     .build()
+///     .unwrap();
+/// ```
+pub trait Middleware: Send + Sync {
+    /// Modifies a request before it's sent.
+    fn request(&self, _req: &mut Request<Vec<u8>>) -> Result<(), ClientError> {
+        Ok(())
+    }
+
+    /// Inspects or modifies a successful response before it's parsed.
+    fn response(&self, _resp: &mut Response<Vec<u8>>) -> Result<(), ClientError> {
+        Ok(())
+    }
+}
+
+impl<F> Middleware for F
+where
+    F: Fn(&mut Request<Vec<u8>>) -> Result<(), ClientError> + Send + Sync,
+{
+    fn request(&self, req: &mut Request<Vec<u8>>) -> Result<(), ClientError> {
+        self(req)
+    }
+}
+
+/// Adds the given headers to every request, replacing the existing values of
+/// the same headers.
+#[derive(Clone, Debug, Default)]
+pub struct Headers {
+    pub headers: HeaderMap,
+}
+
+impl Headers {
+    /// Creates a middleware adding the given headers.
+    pub fn new(headers: HeaderMap) -> Self {
+        Headers { headers }
+    }
+
+    /// Adds the given header.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name or the value isn't a valid header.
+    pub fn with(mut self, name: &str, value: &str) -> Self {
+        self.headers.insert(
+            HeaderName::from_bytes(name.as_bytes()).unwrap(),
+            HeaderValue::from_str(value).unwrap(),
+        );
+        self
+    }
+}
+
+impl Middleware for Headers {
+    fn request(&self, req: &mut Request<Vec<u8>>) -> Result<(), ClientError> {
+        for (name, value) in &self.headers {
+            req.headers_mut().insert(name, value.clone());
+        }
+        Ok(())
+    }
+}
+
+/// An ordered list of [Middleware]s, cheap to clone.
+#[derive(Clone, Default)]
+pub struct MiddlewareChain {
+    middlewares: Vec<Arc<dyn Middleware>>,
+}
+
+impl MiddlewareChain {
+    /// Appends the given middleware to the chain.
+    pub fn push(&mut self, middleware: impl Middleware + 'static) {
+        self.middlewares.push(Arc::new(middleware));
+    }
+
+    /// Inserts the given middleware at the given position of the chain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the length of the chain.
+    pub fn insert(&mut self, index: usize, middleware: impl Middleware + 'static) {
+        self.middlewares.insert(index, Arc::new(middleware));
+    }
+
+    /// Returns the number of middlewares in the chain.
+    pub fn len(&self) -> usize {
+        self.middlewares.len()
+    }
+
+    /// Returns whether the chain is empty.
+    pub fn is_empty(&self) -> bool {
+        self.middlewares.is_empty()
+    }
+
+    /// Runs the request hooks of the chain in order.
+    pub fn request(&self, req: &mut Request<Vec<u8>>) -> Result<(), ClientError> {
+        self.middlewares.iter().try_for_each(|m| m.request(req))
+    }
+
+    /// Runs the response hooks of the chain in reverse order.
+    pub fn response(&self, resp: &mut Response<Vec<u8>>) -> Result<(), ClientError> {
+        self.middlewares
+            .iter()
+            .rev()
+            .try_for_each(|m| m.response(resp))
+    }
+}
+
+impl fmt::Debug for MiddlewareChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MiddlewareChain")
+            .field("len", &self.middlewares.len())
+            .finish()
+    }
+}
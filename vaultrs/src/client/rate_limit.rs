@@ -0,0 +1,93 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+/// Limits the rate at which requests are sent to the server.
+///
+/// Requests are allowed at `rate` per second on average, with bursts of up to
+/// `burst` requests. The state of the limiter is shared between clones so
+/// that every client built from the same settings shares the same budget.
+#[derive(Clone, Debug)]
+pub struct RateLimit {
+    rate: f64,
+    burst: u32,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Option<Instant>,
+}
+
+impl RateLimit {
+    /// Creates a limiter allowing `rate` requests per second with bursts of up
+    /// to `burst` requests.
+    pub fn new(rate: f64, burst: u32) -> Self {
+        let burst = burst.max(1);
+        RateLimit {
+            rate,
+            burst,
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: burst as f64,
+                updated: None,
+            })),
+        }
+    }
+
+    /// Parses a limit using the format of `VAULT_RATE_LIMIT`: the rate
+    /// optionally followed by a colon and the burst, which defaults to the
+    /// rate.
+    pub fn parse(value: &str) -> Option<Self> {
+        let (rate, burst) = match value.split_once(':') {
+            Some((rate, burst)) => (rate, Some(burst)),
+            None => (value, None),
+        };
+        let rate: f64 = rate.trim().parse().ok()?;
+        let burst: Option<u32> = match burst {
+            Some(burst) => Some(burst.trim().parse().ok()?),
+            None => None,
+        };
+        if !rate.is_finite() || rate <= 0.0 {
+            return None;
+        }
+        Some(RateLimit::new(rate, burst.unwrap_or(rate.ceil() as u32)))
+    }
+
+    /// Returns the number of requests allowed per second.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Returns the maximum number of requests allowed in a burst.
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    /// Waits until a request is allowed to be sent.
+    pub async fn acquire(&self) {
+        let wait = {
+            let mut bucket = self.bucket.lock().unwrap();
+            let now = Instant::now();
+            if let Some(updated) = bucket.updated {
+                let elapsed = now.saturating_duration_since(updated).as_secs_f64();
+                bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst as f64);
+            }
+            bucket.updated = Some(now);
+
+            // Reserve the token now, going into debt if needed, so that
+            // concurrent requests queue up behind each other
+            bucket.tokens -= 1.0;
+            if bucket.tokens >= 0.0 {
+                Duration::ZERO
+            } else {
+                Duration::from_secs_f64(-bucket.tokens / self.rate)
+            }
+        };
+        if !wait.is_zero() {
+            trace!("Rate limited, waiting {:?}", wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
@@ -0,0 +1,408 @@
+//! Records the requests sent to a Vault server and replays them later.
+//!
+//! A [Recorder] captures every request sent by a client together with the
+//! response returned by the server and saves them to a fixture file. A
+//! [Replayer] loads the fixture and answers requests with the recorded
+//! responses without contacting any server, allowing the behavior of a client
+//! to be tested deterministically.
+//!
+//! Tokens and secrets are never written to fixtures: the `X-Vault-Token`
+//! header isn't recorded and the values of sensitive fields (see
+//! [DEFAULT_REDACTED_KEYS]) are redacted in both requests and responses. The
+//! request bodies and response `data` of secret engines, which are any paths
+//! outside of `sys/` and `auth/`, and of unwrapped responses are redacted as
+//! well unless [Recorder::redact_data] is disabled. The request bodies of
+//! logins and of data wrapped with `sys/wrapping/wrap` hold credentials and
+//! secrets, and are always redacted in full.
+//!
+//! Strings are replaced with [REDACTED] while numbers and booleans are
+//! replaced with `0` and `false`, so that redacted responses can still be
+//! replayed.
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use vaultrs::client::recording::{Recorder, Recording, Replayer};
+//! use vaultrs::client::{VaultClient, VaultClientSettingsBuilder};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let recorder = Arc::new(Recorder::new("tests/fixtures/pki.json"));
+//! let client = VaultClient::new(
+//!     VaultClientSettingsBuilder::default()
+//!         .recording(Recording::Record(recorder.clone()))
+//!         .build()?,
+//! )?;
+//! // Use the client...
+//! recorder.save()?;
+//!
+//! let replayer = Replayer::load("tests/fixtures/pki.json")?;
+//! let client = VaultClient::new(
+//!     VaultClientSettingsBuilder::default()
+//!         .recording(Recording::Replay(Arc::new(replayer)))
+//!         .build()?,
+//! )?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use http::{Request, Response};
+use rustify::client::Client as RustifyClient;
+use rustify::errors::ClientError as RestClientError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::ClientError;
+
+/// The value which redacted fields are replaced with.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// The fields redacted by default by a [Recorder].
+pub const DEFAULT_REDACTED_KEYS: [&str; 29] = [
+    "access_key",
+    "client_token",
+    "encoded_root_token",
+    "encoded_token",
+    "iam_request_body",
+    "iam_request_headers",
+    "iam_request_url",
+    "id",
+    "identity",
+    "jwt",
+    "key",
+    "keys",
+    "keys_base64",
+    "otp",
+    "password",
+    "pkcs7",
+    "plaintext",
+    "private_key",
+    "recovery_keys",
+    "recovery_keys_base64",
+    "role_id",
+    "root_token",
+    "secret_id",
+    "secret_key",
+    "security_token",
+    "session_token",
+    "signature",
+    "token",
+    "wrapped_accessor",
+];
+
+/// How requests are handled by a client.
+#[derive(Clone, Debug)]
+pub enum Recording {
+    /// Requests are sent to the server and recorded.
+    Record(Arc<Recorder>),
+    /// Requests are answered from a fixture without contacting the server.
+    Replay(Arc<Replayer>),
+}
+
+/// A request sent to the server and the response it returned.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// A request recorded by a [Recorder].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    /// The path of the request, including its query
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+/// A response recorded by a [Recorder]. Bodies which aren't JSON are stored
+/// in `text`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// Records the interactions of a client with the server.
+///
+/// The interactions are kept in memory until [Recorder::save] is called.
+#[derive(Debug)]
+pub struct Recorder {
+    path: PathBuf,
+    redacted_keys: Vec<String>,
+    redact_data: bool,
+    interactions: Mutex<Vec<Interaction>>,
+}
+
+impl Recorder {
+    /// Creates a recorder saving its fixture to the given file.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Recorder {
+            path: path.into(),
+            redacted_keys: DEFAULT_REDACTED_KEYS
+                .iter()
+                .map(|k| k.to_string())
+                .collect(),
+            redact_data: true,
+            interactions: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Redacts the values of the given field in addition to the default ones.
+    pub fn redact(mut self, key: impl Into<String>) -> Self {
+        self.redacted_keys.push(key.into());
+        self
+    }
+
+    /// Sets whether the request bodies and response `data` of secret engines
+    /// are redacted. Enabled by default, disable it to record the responses
+    /// of engines which don't return secrets, like certificates issued by the
+    /// PKI engine.
+    pub fn redact_data(mut self, redact_data: bool) -> Self {
+        self.redact_data = redact_data;
+        self
+    }
+
+    /// Returns the interactions recorded so far.
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.interactions.lock().unwrap().clone()
+    }
+
+    /// Saves the interactions recorded so far to the fixture file.
+    pub fn save(&self) -> Result<(), ClientError> {
+        let content = serde_json::to_string_pretty(&*self.interactions.lock().unwrap())
+            .map_err(|e| ClientError::JsonParseError { source: e })?;
+        fs::write(&self.path, content).map_err(|e| ClientError::FileWriteError {
+            source: e,
+            path: self.path.display().to_string(),
+        })
+    }
+
+    fn record(&self, mut interaction: Interaction, token: Option<&str>) {
+        let path = &interaction.request.path;
+        let secret = self.redact_data && is_secret_path(path);
+        let credentials = is_credentials_path(path);
+        if let Some(body) = &mut interaction.request.body {
+            if secret || credentials {
+                redact_all(body);
+            }
+            redact(body, &self.redacted_keys, token);
+        }
+        if let Some(body) = &mut interaction.response.body {
+            if let (true, Some(data)) = (secret, body.get_mut("data")) {
+                redact_all(data);
+            }
+            redact(body, &self.redacted_keys, token);
+        }
+        if let (Some(text), Some(token)) = (&mut interaction.response.text, token) {
+            *text = text.replace(token, REDACTED);
+        }
+        self.interactions.lock().unwrap().push(interaction);
+    }
+}
+
+/// Answers requests with the responses recorded in a fixture.
+///
+/// Each request is answered with the first interaction which hasn't been
+/// replayed yet and has the same method and path, so that a path returning
+/// different responses over time is replayed in the order it was recorded.
+#[derive(Debug)]
+pub struct Replayer {
+    interactions: Mutex<Vec<Option<Interaction>>>,
+}
+
+impl Replayer {
+    /// Creates a replayer from the given interactions.
+    pub fn new(interactions: Vec<Interaction>) -> Self {
+        Replayer {
+            interactions: Mutex::new(interactions.into_iter().map(Some).collect()),
+        }
+    }
+
+    /// Loads the interactions saved by a [Recorder] to the given file.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, ClientError> {
+        let path = path.into();
+        let content = fs::read_to_string(&path).map_err(|e| ClientError::FileReadError {
+            source: e,
+            path: path.display().to_string(),
+        })?;
+        let interactions = serde_json::from_str(&content)
+            .map_err(|e| ClientError::JsonParseError { source: e })?;
+        Ok(Replayer::new(interactions))
+    }
+
+    /// Returns the number of interactions which haven't been replayed yet.
+    pub fn remaining(&self) -> usize {
+        self.interactions.lock().unwrap().iter().flatten().count()
+    }
+
+    fn replay(&self, method: &str, path: &str) -> Option<RecordedResponse> {
+        self.interactions
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|i| {
+                i.as_ref()
+                    .is_some_and(|i| i.request.method == method && i.request.path == path)
+            })
+            .and_then(Option::take)
+            .map(|i| i.response)
+    }
+}
+
+/// Sends requests through the HTTP client of a [Client][crate::client::Client]
+/// while recording them, or answers them from a fixture, depending on the
+/// [Recording] configured in its settings.
+pub(crate) struct RecordingClient<'a, C: RustifyClient> {
+    http: C,
+    recording: Option<&'a Recording>,
+}
+
+impl<'a, C: RustifyClient> RecordingClient<'a, C> {
+    pub(crate) fn new(http: C, recording: Option<&'a Recording>) -> Self {
+        RecordingClient { http, recording }
+    }
+}
+
+#[async_trait]
+impl<C: RustifyClient> RustifyClient for RecordingClient<'_, C> {
+    async fn send(&self, req: Request<Vec<u8>>) -> Result<Response<Vec<u8>>, RestClientError> {
+        let recording = match self.recording {
+            Some(recording) => recording,
+            None => return self.http.send(req).await,
+        };
+
+        let method = req.method().to_string();
+        let path = req
+            .uri()
+            .path_and_query()
+            .map(|p| p.as_str().to_string())
+            .unwrap_or_default();
+        match recording {
+            Recording::Replay(replayer) => {
+                // Not a connection error, so that a miss is never retried
+                let recorded = replayer.replay(&method, &path).ok_or_else(|| {
+                    RestClientError::GenericError {
+                        source: ClientError::ReplayMissError {
+                            method: method.clone(),
+                            path: path.clone(),
+                        }
+                        .into(),
+                    }
+                })?;
+                let body = match (recorded.body, recorded.text) {
+                    (Some(body), _) => body.to_string().into_bytes(),
+                    (None, Some(text)) => text.into_bytes(),
+                    (None, None) => Vec::new(),
+                };
+                Response::builder()
+                    .status(recorded.status)
+                    .body(body)
+                    .map_err(|e| RestClientError::ResponseError { source: e.into() })
+            }
+            Recording::Record(recorder) => {
+                let token = req
+                    .headers()
+                    .get("X-Vault-Token")
+                    .and_then(|t| t.to_str().ok())
+                    .filter(|t| !t.is_empty())
+                    .map(String::from);
+                let body = serde_json::from_slice(req.body()).ok();
+                let response = self.http.send(req).await?;
+
+                let (body_json, text) = if response.body().is_empty() {
+                    (None, None)
+                } else {
+                    match serde_json::from_slice(response.body()) {
+                        Ok(json) => (Some(json), None),
+                        Err(_) => (
+                            None,
+                            Some(String::from_utf8_lossy(response.body()).into_owned()),
+                        ),
+                    }
+                };
+                recorder.record(
+                    Interaction {
+                        request: RecordedRequest { method, path, body },
+                        response: RecordedResponse {
+                            status: response.status().as_u16(),
+                            body: body_json,
+                            text,
+                        },
+                    },
+                    token.as_deref(),
+                );
+                Ok(response)
+            }
+        }
+    }
+
+    fn base(&self) -> &str {
+        self.http.base()
+    }
+}
+
+/// Returns the given request path without its API version and query.
+fn api_path(path: &str) -> Option<&str> {
+    let path = path.split('?').next().unwrap_or_default();
+    path.trim_start_matches('/').split_once('/').map(|(_, p)| p)
+}
+
+/// Returns whether the given request path, prefixed with the API version,
+/// belongs to a secret engine or unwraps a response.
+fn is_secret_path(path: &str) -> bool {
+    match api_path(path) {
+        Some(p) if p.starts_with("sys/wrapping/unwrap") => true,
+        Some(p) => !(p.starts_with("sys/") || p.starts_with("auth/")),
+        None => false,
+    }
+}
+
+/// Returns whether the body sent to the given request path, prefixed with the
+/// API version, holds credentials or secrets: the login of an auth method or
+/// data to wrap.
+fn is_credentials_path(path: &str) -> bool {
+    match api_path(path) {
+        Some(p) if p.starts_with("auth/") => p.split('/').any(|s| s.starts_with("login")),
+        Some(p) => p == "sys/wrapping/wrap",
+        None => false,
+    }
+}
+
+/// Redacts the values of the given keys, and any occurrence of the token.
+fn redact(value: &mut Value, keys: &[String], token: Option<&str>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if keys.iter().any(|k| k.eq_ignore_ascii_case(key)) {
+                    redact_all(value);
+                } else {
+                    redact(value, keys, token);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(|v| redact(v, keys, token)),
+        Value::String(s) if token.is_some_and(|t| s == t) => *s = REDACTED.to_string(),
+        _ => {}
+    }
+}
+
+/// Redacts every value nested in the given one, keeping their types.
+fn redact_all(value: &mut Value) {
+    match value {
+        Value::Object(map) => map.values_mut().for_each(redact_all),
+        Value::Array(values) => values.iter_mut().for_each(redact_all),
+        Value::String(s) => *s = REDACTED.to_string(),
+        Value::Number(n) => *n = 0.into(),
+        Value::Bool(b) => *b = false,
+        Value::Null => {}
+    }
+}
@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use rustify::enums::RequestMethod;
+use rustify::errors::ClientError as RestClientError;
+
+/// Controls how requests which failed because of transient errors are retried.
+///
+/// Only errors which are likely to be transient are retried: status codes
+/// listed in `status_codes` (by default 412, 429, 500, 502, 503 and 504) and,
+/// when `connection_errors` is set, failures to reach the server at all.
+///
+/// Requests using a non-idempotent method (`POST`, `PUT` and `PATCH`) are
+/// only retried when the server rejected them before processing (429 and 412)
+/// unless `non_idempotent` is set, since replaying them may apply a write
+/// twice.
+///
+/// The delay between attempts grows exponentially from `min_backoff` up to
+/// `max_backoff` and is randomized to avoid retrying in lockstep with other
+/// clients.
+///
+/// By default, like the Go client, a request is retried twice.
+#[derive(Builder, Clone, Debug)]
+#[builder(setter(into), default)]
+pub struct RetryPolicy {
+    /// The maximum number of retries after the initial attempt
+    pub max_retries: u32,
+    /// The delay before the first retry
+    pub min_backoff: Duration,
+    /// The upper bound for the delay between retries
+    pub max_backoff: Duration,
+    /// The HTTP status codes which are considered transient
+    pub status_codes: Vec<u16>,
+    /// Whether failures to connect to the server are retried
+    pub connection_errors: bool,
+    /// Whether non-idempotent requests are retried on any transient error
+    pub non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 2,
+            min_backoff: Duration::from_millis(1000),
+            max_backoff: Duration::from_millis(1500),
+            status_codes: vec![412, 429, 500, 502, 503, 504],
+            connection_errors: true,
+            non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns a builder for a policy, starting from the default policy.
+    pub fn builder() -> RetryPolicyBuilder {
+        RetryPolicyBuilder::default()
+    }
+
+    /// Returns a policy which never retries.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Returns whether a request using the given method which failed with the
+    /// given error should be retried.
+    pub fn should_retry(&self, method: &RequestMethod, error: &RestClientError) -> bool {
+        let idempotent = !matches!(
+            method,
+            RequestMethod::POST | RequestMethod::PUT | RequestMethod::PATCH
+        );
+        match error {
+            RestClientError::ServerResponseError { code, .. } => {
+                if !self.status_codes.contains(code) {
+                    false
+                } else {
+                    idempotent || self.non_idempotent || matches!(code, 412 | 429)
+                }
+            }
+            RestClientError::RequestError { .. } => {
+                self.connection_errors && (idempotent || self.non_idempotent)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the delay to wait before the given retry, starting at zero.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let delay = self
+            .min_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff);
+
+        // Keep at least half of the delay and randomize the rest
+        let half = delay / 2;
+        let jitter = fastrand::f64();
+        half + half.mul_f64(jitter)
+    }
+}
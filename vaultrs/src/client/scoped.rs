@@ -0,0 +1,121 @@
+use std::borrow::Cow;
+
+use async_trait::async_trait;
+use rustify::clients::reqwest::Client as HTTPClient;
+
+use super::middleware::Headers;
+use super::{Client, VaultClientSettings};
+use crate::api::{EndpointMiddleware, SharedToken};
+
+/// A view of a [Client] overriding the namespace, token, wrap TTL or headers
+/// used for the requests sent through it.
+///
+/// The view borrows the HTTP client and the settings of its parent, so
+/// requests share its connection pool and the settings are only copied once
+/// the namespace, token or wrap TTL is overridden. Unless overridden, the
+/// token is shared with the parent so swapping the token of the parent
+/// affects the view, while setting a token on the view detaches it from the
+/// parent.
+///
+/// Headers added to the view are set before the middlewares of the parent
+/// run, so that they see them.
+///
+/// ```no_run
+/// use vaultrs::client::{Client, VaultClient, VaultClientSettingsBuilder};
+/// use vaultrs::kv2;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = VaultClient::new(VaultClientSettingsBuilder::default().build()?)?;
+///
+/// let team = client.with_namespace("team-a");
+/// let secret: serde_json::Value = kv2::read(&team, "secret", "config").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ScopedClient<'a> {
+    http: &'a HTTPClient,
+    middle: EndpointMiddleware,
+    settings: Cow<'a, VaultClientSettings>,
+    shared_token: bool,
+    headers: usize,
+}
+
+impl<'a> ScopedClient<'a> {
+    /// Creates a view of the given client without any override.
+    pub fn new(client: &'a impl Client) -> Self {
+        ScopedClient {
+            http: client.http(),
+            middle: client.middle().clone(),
+            settings: Cow::Borrowed(client.settings()),
+            shared_token: true,
+            headers: 0,
+        }
+    }
+
+    /// Sends the requests in the given namespace instead of the one of the
+    /// parent.
+    pub fn namespace(mut self, namespace: &str) -> Self {
+        self.middle.namespace = Some(namespace.to_string());
+        self.settings.to_mut().namespace = Some(namespace.to_string());
+        self
+    }
+
+    /// Sends the requests without a namespace, i.e. in the root namespace.
+    pub fn root_namespace(mut self) -> Self {
+        self.middle.namespace = None;
+        self.settings.to_mut().namespace = None;
+        self
+    }
+
+    /// Sends the requests with the given token instead of the one of the
+    /// parent. The token is no longer shared with the parent.
+    pub fn token(mut self, token: &str) -> Self {
+        self.set_token(token);
+        self
+    }
+
+    /// Uses the given TTL when wrapping responses.
+    pub fn wrap_ttl(mut self, ttl: &str) -> Self {
+        self.settings.to_mut().wrap_ttl = ttl.to_string();
+        self
+    }
+
+    /// Adds the given header to the requests.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name or the value isn't a valid header.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.middle
+            .chain
+            .insert(self.headers, Headers::default().with(name, value));
+        self.headers += 1;
+        self
+    }
+}
+
+#[async_trait]
+impl Client for ScopedClient<'_> {
+    fn http(&self) -> &HTTPClient {
+        self.http
+    }
+
+    fn middle(&self) -> &EndpointMiddleware {
+        &self.middle
+    }
+
+    fn settings(&self) -> &VaultClientSettings {
+        &self.settings
+    }
+
+    fn set_token(&mut self, token: &str) {
+        self.settings.to_mut().token = token.into();
+        if self.shared_token {
+            self.middle.token = SharedToken::new(token);
+            self.shared_token = false;
+        } else {
+            self.middle.token.set(token);
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::{env, fs};
+
+use crate::{api::SecretString, error::ClientError};
+
+/// A source which tokens can be read from and persisted to, mirroring the
+/// [token helpers](https://developer.hashicorp.com/vault/docs/commands/token-helper)
+/// used by the Vault CLI.
+pub trait TokenHelper: Send + Sync {
+    /// Returns the stored token, if any.
+    fn get(&self) -> Result<Option<SecretString>, ClientError>;
+
+    /// Stores the given token, replacing any existing one.
+    fn store(&self, token: &str) -> Result<(), ClientError>;
+
+    /// Erases the stored token.
+    fn erase(&self) -> Result<(), ClientError>;
+}
+
+/// Reads the token from the `VAULT_TOKEN` environment variable.
+///
+/// The environment is never modified, storing and erasing tokens does
+/// nothing.
+#[derive(Clone, Debug, Default)]
+pub struct EnvTokenHelper;
+
+impl TokenHelper for EnvTokenHelper {
+    fn get(&self) -> Result<Option<SecretString>, ClientError> {
+        Ok(env::var("VAULT_TOKEN")
+            .ok()
+            .filter(|t| !t.is_empty())
+            .map(SecretString::from))
+    }
+
+    fn store(&self, _: &str) -> Result<(), ClientError> {
+        Ok(())
+    }
+
+    fn erase(&self) -> Result<(), ClientError> {
+        Ok(())
+    }
+}
+
+/// Stores the token in a file, usually `~/.vault-token` (see
+/// [FileTokenHelper::home]). This is the token helper used by the Vault CLI
+/// unless another one is configured.
+#[derive(Clone, Debug)]
+pub struct FileTokenHelper {
+    pub path: PathBuf,
+}
+
+impl FileTokenHelper {
+    /// Creates a helper storing the token in the given file.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileTokenHelper { path: path.into() }
+    }
+
+    /// Creates a helper storing the token in `~/.vault-token`, or returns
+    /// `None` if the home directory isn't known.
+    pub fn home() -> Option<Self> {
+        home_dir().map(|home| FileTokenHelper::new(home.join(".vault-token")))
+    }
+}
+
+impl TokenHelper for FileTokenHelper {
+    fn get(&self) -> Result<Option<SecretString>, ClientError> {
+        match fs::read_to_string(&self.path) {
+            Ok(token) => {
+                let token = SecretString::from(token);
+                Ok(Some(SecretString::from(token.trim())).filter(|t| !t.is_empty()))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(ClientError::FileReadError {
+                source: e,
+                path: self.path.display().to_string(),
+            }),
+        }
+    }
+
+    fn store(&self, token: &str) -> Result<(), ClientError> {
+        write_private(&self.path, token).map_err(|e| ClientError::FileWriteError {
+            source: e,
+            path: self.path.display().to_string(),
+        })
+    }
+
+    fn erase(&self) -> Result<(), ClientError> {
+        match fs::remove_file(&self.path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(ClientError::FileWriteError {
+                source: e,
+                path: self.path.display().to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Delegates to an external program which is invoked with `get`, `store` or
+/// `erase` as its only argument. The token is read from its standard output
+/// when getting it and written to its standard input when storing it.
+#[derive(Clone, Debug)]
+pub struct ExternalTokenHelper {
+    pub program: PathBuf,
+}
+
+impl ExternalTokenHelper {
+    /// Creates a helper delegating to the given program.
+    pub fn new(program: impl Into<PathBuf>) -> Self {
+        ExternalTokenHelper {
+            program: program.into(),
+        }
+    }
+
+    fn run(&self, command: &str, input: Option<&str>) -> Result<SecretString, ClientError> {
+        let program = self.program.display().to_string();
+        let error = |message: String| ClientError::TokenHelperError {
+            program: program.clone(),
+            message,
+        };
+
+        debug!("Running token helper {} {}", program, command);
+        let mut child = Command::new(&self.program)
+            .arg(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| error(e.to_string()))?;
+        if let Some(input) = input {
+            // Dropping stdin closes it, signaling the end of the token
+            let mut stdin = child.stdin.take().unwrap();
+            stdin
+                .write_all(input.as_bytes())
+                .map_err(|e| error(e.to_string()))?;
+        }
+        let output = child.wait_with_output().map_err(|e| error(e.to_string()))?;
+        if !output.status.success() {
+            return Err(error(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+        let stdout = SecretString::from(String::from_utf8_lossy(&output.stdout).into_owned());
+        Ok(SecretString::from(stdout.trim()))
+    }
+}
+
+impl TokenHelper for ExternalTokenHelper {
+    fn get(&self) -> Result<Option<SecretString>, ClientError> {
+        self.run("get", None)
+            .map(|t| Some(t).filter(|t| !t.is_empty()))
+    }
+
+    fn store(&self, token: &str) -> Result<(), ClientError> {
+        self.run("store", Some(token)).map(|_| ())
+    }
+
+    fn erase(&self) -> Result<(), ClientError> {
+        self.run("erase", None).map(|_| ())
+    }
+}
+
+/// Tries a list of [TokenHelper]s in order, returning the first token found.
+///
+/// Tokens are stored and erased using every helper of the chain.
+pub struct TokenHelperChain {
+    helpers: Vec<Box<dyn TokenHelper>>,
+}
+
+impl TokenHelperChain {
+    /// Creates a chain trying the given helpers in order.
+    pub fn new(helpers: Vec<Box<dyn TokenHelper>>) -> Self {
+        TokenHelperChain { helpers }
+    }
+}
+
+impl Default for TokenHelperChain {
+    /// Returns the chain used by the Vault CLI: the `VAULT_TOKEN` environment
+    /// variable followed by the token helper configured in the CLI
+    /// configuration file (`VAULT_CONFIG_PATH` or `~/.vault`), falling back to
+    /// `~/.vault-token`. Only the environment variable is used when the home
+    /// directory isn't known.
+    ///
+    /// Tokens are only read once [TokenHelper::get] is called, which may run
+    /// the configured external program.
+    fn default() -> Self {
+        let mut helpers: Vec<Box<dyn TokenHelper>> = vec![Box::new(EnvTokenHelper)];
+        helpers.extend(configured_helper());
+        TokenHelperChain::new(helpers)
+    }
+}
+
+impl TokenHelper for TokenHelperChain {
+    fn get(&self) -> Result<Option<SecretString>, ClientError> {
+        for helper in &self.helpers {
+            if let Some(token) = helper.get()? {
+                return Ok(Some(token));
+            }
+        }
+        Ok(None)
+    }
+
+    fn store(&self, token: &str) -> Result<(), ClientError> {
+        self.helpers.iter().try_for_each(|h| h.store(token))
+    }
+
+    fn erase(&self) -> Result<(), ClientError> {
+        self.helpers.iter().try_for_each(|h| h.erase())
+    }
+}
+
+/// Returns the token helper configured in the Vault CLI configuration file,
+/// or a [FileTokenHelper] storing the token in `~/.vault-token` if none is
+/// configured. Returns `None` if neither is available because the home
+/// directory isn't known.
+pub fn configured_helper() -> Option<Box<dyn TokenHelper>> {
+    let path = env::var("VAULT_CONFIG_PATH")
+        .map(PathBuf::from)
+        .ok()
+        .or_else(|| home_dir().map(|home| home.join(".vault")));
+    let program = path
+        .as_ref()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| parse_token_helper(&c));
+    match (program, path) {
+        (Some(program), Some(path)) => {
+            debug!("Using token helper {} from {}", program, path.display());
+            Some(Box::new(ExternalTokenHelper::new(program)))
+        }
+        _ => FileTokenHelper::home().map(|h| Box::new(h) as Box<dyn TokenHelper>),
+    }
+}
+
+/// Extracts the `token_helper` setting from the contents of a Vault CLI
+/// configuration file.
+fn parse_token_helper(config: &str) -> Option<String> {
+    config.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        if key.trim() != "token_helper" {
+            return None;
+        }
+        let value = value.trim().trim_matches('"');
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Writes the file so that only the current user can read it, restricting
+/// the permissions of an existing file before writing to it.
+fn write_private(path: &Path, content: &str) -> std::io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(content.as_bytes())
+}
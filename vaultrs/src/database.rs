@@ -106,6 +106,7 @@ pub mod role {
         },
         responses::{GenerateCredentialsResponse, ListRolesResponse, ReadRoleResponse},
     };
+    use crate::api::Secret;
     use crate::client::Client;
     use crate::error::ClientError;
 
@@ -125,6 +126,22 @@ pub mod role {
         api::exec_with_result(client, endpoint).await
     }
 
+    /// Generates credentials from a role, returning them with their lease
+    ///
+    /// See [GenerateCredentialsRequest]
+    pub async fn creds_with_lease(
+        client: &impl Client,
+        mount: &str,
+        name: &str,
+    ) -> Result<Secret<GenerateCredentialsResponse>, ClientError> {
+        let endpoint = GenerateCredentialsRequest::builder()
+            .mount(mount)
+            .name(name)
+            .build()
+            .unwrap();
+        api::exec_with_lease(client, endpoint).await
+    }
+
     /// Deletes a role
     ///
     /// See [DeleteRoleRequest]
@@ -17,10 +17,18 @@ pub enum ClientError {
         source: std::io::Error,
         path: String,
     },
+    #[error("Invalid TLS server name {name} for {host}: {reason}")]
+    InvalidTlsServerName {
+        name: String,
+        host: String,
+        reason: String,
+    },
     #[error("Invalid login method")]
     InvalidLoginMethodError,
     #[error("Error parsing value into JSON")]
     JsonParseError { source: serde_json::error::Error },
+    #[error("A middleware failed processing the request: {message}")]
+    MiddlewareError { message: String },
     #[error("Error parsing CA certificate as PEM encoded certificate: {path}")]
     ParseCertificateError {
         source: reqwest::Error,
@@ -39,8 +47,149 @@ pub enum ClientError {
         #[from]
         source: rustify::errors::ClientError,
     },
+    #[error("No recorded interaction matches {method} {path}")]
+    ReplayMissError { method: String, path: String },
+    #[error("Error decoding the encoded root token")]
+    RootTokenDecodeError,
+    #[error("Error starting the runtime of the blocking client")]
+    RuntimeBuildError { source: std::io::Error },
+    #[error("Error executing token helper {program}: {message}")]
+    TokenHelperError { program: String, message: String },
     #[error("The wrapped response doesn't exist or is not longer valid")]
     WrapInvalidError,
+    #[error("The wrapped response was created by {actual} instead of {expected}")]
+    WrapPathMismatchError { expected: String, actual: String },
     #[error("The parameters given to the endpoint didn't update anything")]
     InvalidUpdateParameter,
 }
+
+impl ClientError {
+    /// Returns the [ApiErrorKind] of this error if it was returned by the
+    /// Vault API.
+    pub fn kind(&self) -> Option<ApiErrorKind> {
+        match self {
+            ClientError::APIError { code, errors } => Some(ApiErrorKind::classify(*code, errors)),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the requested path or resource doesn't exist.
+    pub fn is_not_found(&self) -> bool {
+        self.kind() == Some(ApiErrorKind::NotFound)
+    }
+
+    /// Returns whether the token isn't allowed to perform the request.
+    pub fn is_permission_denied(&self) -> bool {
+        self.kind() == Some(ApiErrorKind::PermissionDenied)
+    }
+
+    /// Returns whether the request failed because the Vault server is sealed.
+    pub fn is_sealed(&self) -> bool {
+        self.kind() == Some(ApiErrorKind::Sealed)
+    }
+
+    /// Returns whether the request failed because the Vault server is a
+    /// standby node which can't service it.
+    pub fn is_standby(&self) -> bool {
+        self.kind() == Some(ApiErrorKind::Standby)
+    }
+
+    /// Returns whether the request failed because the Vault server is
+    /// temporarily unable to service it.
+    pub fn is_unavailable(&self) -> bool {
+        self.kind() == Some(ApiErrorKind::Unavailable)
+    }
+
+    /// Returns whether the request was rejected by a rate limit quota.
+    pub fn is_rate_limited(&self) -> bool {
+        self.kind() == Some(ApiErrorKind::RateLimited)
+    }
+
+    /// Returns whether the request was rejected as invalid.
+    pub fn is_invalid_request(&self) -> bool {
+        self.kind() == Some(ApiErrorKind::InvalidRequest)
+    }
+
+    /// Returns whether a check-and-set write was rejected because the secret
+    /// version didn't match.
+    pub fn is_cas_mismatch(&self) -> bool {
+        self.kind() == Some(ApiErrorKind::CasMismatch)
+    }
+
+    /// Returns whether the lease referenced by the request expired or doesn't
+    /// exist anymore.
+    pub fn is_lease_expired(&self) -> bool {
+        self.kind() == Some(ApiErrorKind::LeaseExpired)
+    }
+
+    /// Returns whether the wrapping token referenced by the request is not
+    /// valid anymore.
+    pub fn is_wrap_invalid(&self) -> bool {
+        matches!(self, ClientError::WrapInvalidError)
+            || self.kind() == Some(ApiErrorKind::WrappingTokenInvalid)
+    }
+}
+
+/// The category of an error returned by the Vault API.
+///
+/// It is derived from the status code and the error messages of the response
+/// so that callers don't need to match on either themselves.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ApiErrorKind {
+    /// The requested path or resource doesn't exist (404).
+    NotFound,
+    /// The token isn't allowed to perform the request (403).
+    PermissionDenied,
+    /// The Vault server is sealed.
+    Sealed,
+    /// The Vault server is a standby node which can't service the request.
+    Standby,
+    /// The Vault server can't service the request for another reason, like
+    /// being in maintenance or overloaded (503).
+    Unavailable,
+    /// The request was rejected by a rate limit quota (429).
+    RateLimited,
+    /// The request was rejected as invalid (400).
+    InvalidRequest,
+    /// A check-and-set write was rejected because the secret version didn't
+    /// match.
+    CasMismatch,
+    /// The lease referenced by the request expired or doesn't exist anymore.
+    LeaseExpired,
+    /// The wrapping token referenced by the request is not valid anymore.
+    WrappingTokenInvalid,
+    /// Any other error.
+    Other,
+}
+
+impl ApiErrorKind {
+    /// Classifies an error returned by the Vault API using its status code and
+    /// error messages.
+    pub fn classify(code: u16, errors: &[String]) -> ApiErrorKind {
+        let contains = |needle: &str| errors.iter().any(|e| e.to_lowercase().contains(needle));
+
+        if contains("check-and-set") {
+            ApiErrorKind::CasMismatch
+        } else if contains("wrapping token is not valid") {
+            ApiErrorKind::WrappingTokenInvalid
+        } else if contains("lease not found")
+            || contains("lease expired")
+            || contains("invalid lease")
+        {
+            ApiErrorKind::LeaseExpired
+        } else if contains("vault is sealed") {
+            ApiErrorKind::Sealed
+        } else if contains("standby") || contains("node not active") {
+            ApiErrorKind::Standby
+        } else {
+            match code {
+                400 => ApiErrorKind::InvalidRequest,
+                403 => ApiErrorKind::PermissionDenied,
+                404 => ApiErrorKind::NotFound,
+                429 => ApiErrorKind::RateLimited,
+                503 => ApiErrorKind::Unavailable,
+                _ => ApiErrorKind::Other,
+            }
+        }
+    }
+}
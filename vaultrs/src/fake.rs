@@ -0,0 +1,1341 @@
+//! An in-process fake Vault server for testing code which uses this crate
+//! without a real Vault server.
+//!
+//! The [FakeVault] serves the Vault HTTP API on a random local port and keeps
+//! all of its state in memory. It emulates the semantics of the following
+//! engines closely enough for code written against the corresponding modules
+//! of this crate to be tested offline:
+//!
+//! * KV v1 and KV v2 (including versioning, check-and-set and metadata)
+//! * Cubbyhole
+//! * Token (create, lookup, renew and revoke)
+//! * Transit (keys, rotation, encrypt and decrypt)
+//! * Sys mounts and policies
+//! * Sys seal status, sealing and unsealing with key shares
+//!
+//! Policies are stored but not enforced, any valid token can access every
+//! path. Transit ciphertexts are not encrypted with a real cipher and are only
+//! meant to be decrypted by the same [FakeVault]. Paths which aren't emulated
+//! respond with a 404 error.
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use vaultrs::fake::FakeVault;
+//! use vaultrs::kv2;
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let vault = FakeVault::start().await.unwrap();
+//! let client = vault.client();
+//!
+//! let secret = HashMap::from([("key", "value")]);
+//! kv2::set(&client, "secret", "mysecret", &secret).await.unwrap();
+//!
+//! let read: HashMap<String, String> = kv2::read(&client, "secret", "mysecret").await.unwrap();
+//! assert_eq!(read["key"], "value");
+//! # }
+//! ```
+
+use std::collections::hash_map::RandomState;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hasher};
+use std::io::Read;
+#[cfg(unix)]
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Map, Value};
+use tiny_http::{Header, Server};
+
+use crate::client::{VaultClient, VaultClientSettingsBuilder};
+
+/// The root token of a [FakeVault].
+pub const ROOT_TOKEN: &str = "root";
+
+/// The largest request body accepted, the default of a Vault listener.
+const MAX_REQUEST_SIZE: usize = 32 * 1024 * 1024;
+
+/// A fake Vault server running in a background thread. The server is stopped
+/// when this is dropped.
+pub struct FakeVault {
+    address: String,
+    state: Arc<Mutex<State>>,
+    server: Arc<Server>,
+}
+
+impl FakeVault {
+    /// Starts a fake Vault server listening on a random local port.
+    ///
+    /// The server starts with a KV v2 engine mounted at `secret/` and a
+    /// cubbyhole engine mounted at `cubbyhole/`, like a Vault development
+    /// server.
+    pub async fn start() -> std::io::Result<FakeVault> {
+        let server = Server::http("127.0.0.1:0").map_err(std::io::Error::other)?;
+        let addr = server.server_addr().to_ip().unwrap();
+        debug!("Started fake Vault server on {}", addr);
+        Ok(FakeVault::serve(server, format!("http://{addr}")))
+    }
+
+    /// Starts a fake Vault server listening on a unix socket at the given
+    /// path, like a Vault Agent or Proxy configured with a unix listener.
+    #[cfg(unix)]
+    pub async fn start_unix(path: impl AsRef<Path>) -> std::io::Result<FakeVault> {
+        let server = Server::http_unix(path.as_ref()).map_err(std::io::Error::other)?;
+        debug!("Started fake Vault server on {}", path.as_ref().display());
+        Ok(FakeVault::serve(
+            server,
+            format!("unix://{}", path.as_ref().display()),
+        ))
+    }
+
+    fn serve(server: Server, address: String) -> FakeVault {
+        let server = Arc::new(server);
+        let state = Arc::new(Mutex::new(State::new()));
+        let (incoming, shared) = (server.clone(), state.clone());
+        std::thread::spawn(move || {
+            for request in incoming.incoming_requests() {
+                handle_request(request, &shared);
+            }
+        });
+        FakeVault {
+            address,
+            state,
+            server,
+        }
+    }
+
+    /// Returns the address of the server.
+    pub fn address(&self) -> String {
+        self.address.clone()
+    }
+
+    /// Returns a new client configured to use this server with the root
+    /// token.
+    pub fn client(&self) -> VaultClient {
+        VaultClient::new(
+            VaultClientSettingsBuilder::default()
+                .address(self.address())
+                .token(ROOT_TOKEN)
+                .build()
+                .unwrap(),
+        )
+        .unwrap()
+    }
+
+    /// Seals the server with the given unseal key shares, `threshold` of
+    /// which must be submitted to `sys/unseal` to unseal it again.
+    ///
+    /// While sealed, every path but `sys/health`, `sys/seal-status` and
+    /// `sys/unseal` responds with a 503 error.
+    pub fn seal<T: Into<String>>(&self, shares: impl IntoIterator<Item = T>, threshold: u64) {
+        self.state.lock().unwrap().seal = Seal::new(shares, threshold, false);
+    }
+
+    /// Seals the server like [seal][FakeVault::seal] with a pending seal
+    /// migration, the key shares are only accepted with the `migrate` option.
+    pub fn seal_for_migration<T: Into<String>>(
+        &self,
+        shares: impl IntoIterator<Item = T>,
+        threshold: u64,
+    ) {
+        self.state.lock().unwrap().seal = Seal::new(shares, threshold, true);
+    }
+}
+
+impl Drop for FakeVault {
+    fn drop(&mut self) {
+        self.server.unblock();
+    }
+}
+
+fn handle_request(mut request: tiny_http::Request, state: &Mutex<State>) {
+    let mut body = Vec::new();
+    let read = request
+        .as_reader()
+        .take(MAX_REQUEST_SIZE as u64 + 1)
+        .read_to_end(&mut body);
+    let response = match read {
+        Err(e) => Response::error(400, &e.to_string()),
+        Ok(length) if length > MAX_REQUEST_SIZE => {
+            Response::error(413, "http: request body too large")
+        }
+        Ok(_) => {
+            let token = request
+                .headers()
+                .iter()
+                .find(|h| h.field.equiv("X-Vault-Token"))
+                .map(|h| h.value.to_string());
+            let req = Request::new(
+                request.method().as_str().to_string(),
+                request.url(),
+                token,
+                &body,
+            );
+            state.lock().unwrap().handle(&req)
+        }
+    };
+    if let Err(e) = request.respond(response.into_http()) {
+        debug!("Fake Vault server failed responding: {}", e);
+    }
+}
+
+struct Request {
+    method: String,
+    path: Vec<String>,
+    query: HashMap<String, String>,
+    token: Option<String>,
+    body: Value,
+}
+
+impl Request {
+    fn new(method: String, target: &str, token: Option<String>, body: &[u8]) -> Self {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let query: HashMap<String, String> = query
+            .split('&')
+            .filter_map(|p| p.split_once('='))
+            .map(|(k, v)| (decode(k), decode(v)))
+            .collect();
+
+        // Some endpoints list their items using a GET request with a list
+        // parameter instead of the LIST method
+        let method = if method == "GET" && query.get("list").is_some_and(|l| l == "true") {
+            "LIST".to_string()
+        } else {
+            method
+        };
+
+        let path = path
+            .split('/')
+            .map(decode)
+            .filter(|s| !s.trim().is_empty())
+            .skip(1) // The API version
+            .collect();
+
+        Request {
+            method,
+            path,
+            query,
+            token,
+            body: serde_json::from_slice(body).unwrap_or(Value::Null),
+        }
+    }
+
+    fn str(&self, field: &str) -> Option<&str> {
+        self.body.get(field).and_then(Value::as_str)
+    }
+}
+
+struct Response {
+    status: u16,
+    body: Option<Value>,
+}
+
+impl Response {
+    fn data(data: Value) -> Self {
+        Response::envelope(data, Value::Null)
+    }
+
+    fn auth(auth: Value) -> Self {
+        Response::envelope(Value::Null, auth)
+    }
+
+    fn envelope(data: Value, auth: Value) -> Self {
+        Response {
+            status: 200,
+            body: Some(json!({
+                "request_id": random_id(),
+                "lease_id": "",
+                "renewable": false,
+                "lease_duration": 0,
+                "data": data,
+                "wrap_info": null,
+                "warnings": null,
+                "auth": auth,
+            })),
+        }
+    }
+
+    fn empty() -> Self {
+        Response {
+            status: 204,
+            body: None,
+        }
+    }
+
+    fn error(status: u16, error: &str) -> Self {
+        Response {
+            status,
+            body: Some(json!({ "errors": [error] })),
+        }
+    }
+
+    fn not_found() -> Self {
+        Response {
+            status: 404,
+            body: Some(json!({ "errors": [] })),
+        }
+    }
+
+    fn unsupported() -> Self {
+        Response::error(404, "unsupported path")
+    }
+
+    fn into_http(self) -> tiny_http::Response<std::io::Cursor<Vec<u8>>> {
+        let body = self.body.map(|b| b.to_string()).unwrap_or_default();
+        tiny_http::Response::from_string(body)
+            .with_status_code(self.status)
+            .with_header(Header::from_bytes("Content-Type", "application/json").unwrap())
+    }
+}
+
+struct State {
+    mounts: BTreeMap<String, Mount>,
+    policies: BTreeMap<String, String>,
+    tokens: HashMap<String, Token>,
+    seal: Seal,
+}
+
+struct Seal {
+    shares: Vec<String>,
+    threshold: u64,
+    submitted: Vec<String>,
+    sealed: bool,
+    migration: bool,
+}
+
+impl Seal {
+    fn new<T: Into<String>>(
+        shares: impl IntoIterator<Item = T>,
+        threshold: u64,
+        migration: bool,
+    ) -> Self {
+        Seal {
+            shares: shares.into_iter().map(Into::into).collect(),
+            threshold,
+            submitted: Vec::new(),
+            sealed: true,
+            migration,
+        }
+    }
+
+    fn status(&self) -> Response {
+        Response {
+            status: 200,
+            body: Some(json!({
+                "type": "shamir",
+                "initialized": true,
+                "sealed": self.sealed,
+                "t": self.threshold,
+                "n": self.shares.len(),
+                "progress": self.submitted.len(),
+                "nonce": "",
+                "version": "1.15.0",
+                "migration": self.migration,
+                "recovery_seal": false,
+                "storage_type": "inmem",
+            })),
+        }
+    }
+
+    fn unseal(&mut self, req: &Request) -> Response {
+        if req.body.get("reset").and_then(Value::as_bool) == Some(true) {
+            self.submitted.clear();
+            return self.status();
+        }
+        if !self.sealed {
+            return self.status();
+        }
+        let Some(key) = req.str("key") else {
+            return Response::error(
+                400,
+                "'key' must be specified in request body as JSON, or 'reset' set to true",
+            );
+        };
+        if self.migration && req.body.get("migrate").and_then(Value::as_bool) != Some(true) {
+            return Response::error(
+                400,
+                "'migrate' parameter must be set true in JSON body when in seal migration mode",
+            );
+        }
+        if !self.shares.iter().any(|s| s == key) {
+            return Response::error(400, "invalid key");
+        }
+        if self.submitted.iter().any(|s| s == key) {
+            return Response::error(
+                400,
+                "given key has already been provided during this generation operation",
+            );
+        }
+
+        self.submitted.push(key.to_string());
+        if self.submitted.len() as u64 >= self.threshold {
+            self.submitted.clear();
+            self.sealed = false;
+            self.migration = false;
+        }
+        self.status()
+    }
+}
+
+struct Mount {
+    mount_type: String,
+    description: String,
+    options: Map<String, Value>,
+    accessor: String,
+    uuid: String,
+    backend: Backend,
+}
+
+enum Backend {
+    Kv1(BTreeMap<String, Value>),
+    Kv2(Kv2Store),
+    Cubbyhole(HashMap<String, BTreeMap<String, Value>>),
+    Transit(BTreeMap<String, TransitKey>),
+}
+
+#[derive(Default)]
+struct Kv2Store {
+    max_versions: u64,
+    cas_required: bool,
+    secrets: BTreeMap<String, Kv2Secret>,
+}
+
+#[derive(Default)]
+struct Kv2Secret {
+    created_time: String,
+    updated_time: String,
+    current_version: u64,
+    max_versions: u64,
+    cas_required: bool,
+    custom_metadata: Option<Value>,
+    versions: BTreeMap<u64, Kv2Version>,
+}
+
+struct Kv2Version {
+    data: Value,
+    created_time: String,
+    deletion_time: String,
+    destroyed: bool,
+}
+
+impl Kv2Version {
+    fn metadata(&self, version: u64, secret: &Kv2Secret) -> Value {
+        json!({
+            "created_time": self.created_time,
+            "deletion_time": self.deletion_time,
+            "destroyed": self.destroyed,
+            "version": version,
+            "custom_metadata": secret.custom_metadata,
+        })
+    }
+}
+
+struct TransitKey {
+    key_type: String,
+    deletion_allowed: bool,
+    exportable: bool,
+    allow_plaintext_backup: bool,
+    min_decryption_version: u64,
+    min_encryption_version: u64,
+    versions: BTreeMap<u64, (u64, Vec<u8>)>,
+}
+
+impl TransitKey {
+    fn new(key_type: &str) -> Self {
+        let mut key = TransitKey {
+            key_type: key_type.to_string(),
+            deletion_allowed: false,
+            exportable: false,
+            allow_plaintext_backup: false,
+            min_decryption_version: 1,
+            min_encryption_version: 0,
+            versions: BTreeMap::new(),
+        };
+        key.rotate();
+        key
+    }
+
+    fn latest(&self) -> u64 {
+        self.versions.keys().last().copied().unwrap_or_default()
+    }
+
+    fn rotate(&mut self) {
+        let material = (0..4).flat_map(|_| random_u64().to_le_bytes()).collect();
+        self.versions
+            .insert(self.latest() + 1, (unix_time(), material));
+    }
+}
+
+struct Token {
+    id: String,
+    accessor: String,
+    policies: Vec<String>,
+    meta: Option<Value>,
+    display_name: String,
+    creation_time: u64,
+    ttl: u64,
+    explicit_max_ttl: u64,
+    renewable: bool,
+    orphan: bool,
+    num_uses: u64,
+    path: String,
+}
+
+impl Token {
+    fn remaining(&self) -> u64 {
+        if self.ttl == 0 {
+            return 0;
+        }
+        (self.creation_time + self.ttl).saturating_sub(unix_time())
+    }
+
+    fn auth(&self) -> Value {
+        json!({
+            "client_token": self.id,
+            "accessor": self.accessor,
+            "policies": self.policies,
+            "token_policies": self.policies,
+            "metadata": self.meta,
+            "lease_duration": self.ttl,
+            "renewable": self.renewable,
+            "entity_id": "",
+            "token_type": "service",
+            "orphan": self.orphan,
+        })
+    }
+
+    fn lookup(&self) -> Value {
+        json!({
+            "accessor": self.accessor,
+            "creation_time": self.creation_time,
+            "creation_ttl": self.ttl,
+            "display_name": self.display_name,
+            "entity_id": "",
+            "expire_time": null,
+            "explicit_max_ttl": self.explicit_max_ttl,
+            "id": self.id,
+            "identity_policies": null,
+            "issue_time": rfc3339(self.creation_time),
+            "meta": self.meta,
+            "num_uses": self.num_uses,
+            "orphan": self.orphan,
+            "path": self.path,
+            "policies": self.policies,
+            "renewable": self.renewable,
+            "role": null,
+            "ttl": self.remaining(),
+        })
+    }
+}
+
+impl State {
+    fn new() -> Self {
+        let mut state = State {
+            mounts: BTreeMap::new(),
+            policies: BTreeMap::from([
+                ("default".to_string(), String::new()),
+                ("root".to_string(), String::new()),
+            ]),
+            tokens: HashMap::new(),
+            seal: Seal {
+                shares: vec![random_id()],
+                threshold: 1,
+                submitted: Vec::new(),
+                sealed: false,
+                migration: false,
+            },
+        };
+        state.tokens.insert(
+            ROOT_TOKEN.to_string(),
+            Token {
+                id: ROOT_TOKEN.to_string(),
+                accessor: random_id(),
+                policies: vec!["root".to_string()],
+                meta: None,
+                display_name: "root".to_string(),
+                creation_time: unix_time(),
+                ttl: 0,
+                explicit_max_ttl: 0,
+                renewable: false,
+                orphan: true,
+                num_uses: 0,
+                path: "auth/token/root".to_string(),
+            },
+        );
+        state.mount("secret/", "kv", "key/value secret storage", {
+            let mut options = Map::new();
+            options.insert("version".to_string(), json!("2"));
+            options
+        });
+        state.mount(
+            "cubbyhole/",
+            "cubbyhole",
+            "per-token private secret storage",
+            Map::new(),
+        );
+        state
+    }
+
+    fn mount(
+        &mut self,
+        path: &str,
+        mount_type: &str,
+        description: &str,
+        options: Map<String, Value>,
+    ) {
+        let backend = match mount_type {
+            "kv" if options.get("version").and_then(Value::as_str) == Some("2") => {
+                Backend::Kv2(Kv2Store::default())
+            }
+            "kv-v2" => Backend::Kv2(Kv2Store::default()),
+            "cubbyhole" => Backend::Cubbyhole(HashMap::new()),
+            "transit" => Backend::Transit(BTreeMap::new()),
+            _ => Backend::Kv1(BTreeMap::new()),
+        };
+        self.mounts.insert(
+            path.to_string(),
+            Mount {
+                mount_type: mount_type.to_string(),
+                description: description.to_string(),
+                options,
+                accessor: format!("{}_{}", mount_type, &random_id()[..8]),
+                uuid: random_id(),
+                backend,
+            },
+        );
+    }
+
+    fn handle(&mut self, req: &Request) -> Response {
+        trace!("Fake Vault server handling {} {:?}", req.method, req.path);
+        let path: Vec<&str> = req.path.iter().map(String::as_str).collect();
+        let unauthenticated = matches!(
+            path.as_slice(),
+            ["sys", "health"] | ["sys", "seal-status"] | ["sys", "unseal"]
+        );
+        if self.seal.sealed && !unauthenticated {
+            return Response::error(503, "Vault is sealed");
+        }
+        let token = match req
+            .token
+            .as_deref()
+            .filter(|t| self.tokens.contains_key(*t))
+        {
+            Some(token) => token.to_string(),
+            None if unauthenticated => String::new(),
+            None => return Response::error(403, "permission denied"),
+        };
+
+        match path.as_slice() {
+            ["sys", rest @ ..] => self.handle_sys(req, rest),
+            ["auth", "token", rest @ ..] => self.handle_token(req, &token, rest),
+            _ => self.handle_mount(req, &token),
+        }
+    }
+
+    fn handle_sys(&mut self, req: &Request, path: &[&str]) -> Response {
+        match (req.method.as_str(), path) {
+            ("GET", ["health"]) => Response {
+                status: if self.seal.sealed { 503 } else { 200 },
+                body: Some(json!({
+                    "initialized": true,
+                    "sealed": self.seal.sealed,
+                    "standby": false,
+                    "performance_standby": false,
+                    "replication_performance_mode": "disabled",
+                    "replication_dr_mode": "disabled",
+                    "server_time_utc": unix_time(),
+                    "version": "1.15.0",
+                    "cluster_name": "fake-vault",
+                    "cluster_id": "fake-vault",
+                })),
+            },
+            ("GET", ["seal-status"]) => self.seal.status(),
+            ("PUT", ["seal"]) => {
+                self.seal.submitted.clear();
+                self.seal.sealed = true;
+                Response::empty()
+            }
+            ("PUT", ["unseal"]) | ("POST", ["unseal"]) => self.seal.unseal(req),
+            ("GET", ["mounts"]) => Response::data(Value::Object(
+                self.mounts
+                    .iter()
+                    .map(|(path, mount)| (path.clone(), mount_info(mount)))
+                    .collect(),
+            )),
+            ("GET", ["mounts", rest @ ..]) => match self.mounts.get(&mount_path(rest)) {
+                Some(mount) => Response::data(mount_info(mount)),
+                None => Response::error(400, "no matching mount"),
+            },
+            ("POST", ["mounts", rest @ ..]) => {
+                let path = mount_path(rest);
+                if self.mounts.contains_key(&path) {
+                    return Response::error(400, &format!("path is already in use at {path}"));
+                }
+                let Some(mount_type) = req.str("type") else {
+                    return Response::error(400, "backend type must be specified as a string");
+                };
+                let options = req
+                    .body
+                    .get("options")
+                    .and_then(Value::as_object)
+                    .cloned()
+                    .unwrap_or_default();
+                let description = req.str("description").unwrap_or_default().to_string();
+                self.mount(&path, mount_type, &description, options);
+                Response::empty()
+            }
+            ("DELETE", ["mounts", rest @ ..]) => {
+                self.mounts.remove(&mount_path(rest));
+                Response::empty()
+            }
+            ("GET", ["policy"]) | ("LIST", ["policy"]) => Response::data(json!({
+                "policies": self.policies.keys().collect::<Vec<_>>(),
+                "keys": self.policies.keys().collect::<Vec<_>>(),
+            })),
+            ("GET", ["policy", name]) => match self.policies.get(*name) {
+                Some(rules) => Response::data(json!({ "name": name, "rules": rules })),
+                None => Response::not_found(),
+            },
+            ("PUT", ["policy", name]) | ("POST", ["policy", name]) => {
+                let Some(policy) = req.str("policy").or_else(|| req.str("rules")) else {
+                    return Response::error(400, "'policy' parameter not supplied or empty");
+                };
+                self.policies.insert(name.to_string(), policy.to_string());
+                Response::empty()
+            }
+            ("DELETE", ["policy", name]) => {
+                if matches!(*name, "default" | "root") {
+                    return Response::error(400, &format!("cannot delete {name} policy"));
+                }
+                self.policies.remove(*name);
+                Response::empty()
+            }
+            _ => Response::unsupported(),
+        }
+    }
+
+    fn handle_token(&mut self, req: &Request, current: &str, path: &[&str]) -> Response {
+        match (req.method.as_str(), path) {
+            ("POST", ["create"]) | ("POST", ["create-orphan"]) => {
+                let parent = self.tokens.get(current).unwrap();
+                let policies = match req.body.get("policies").and_then(Value::as_array) {
+                    Some(p) => p
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .map(String::from)
+                        .collect(),
+                    None => parent.policies.clone(),
+                };
+                let orphan = path[0] == "create-orphan"
+                    || req.body.get("no_parent").and_then(Value::as_bool) == Some(true);
+                let ttl = req
+                    .str("ttl")
+                    .and_then(parse_ttl)
+                    .unwrap_or(if parent.ttl == 0 { 2764800 } else { parent.ttl });
+                let token = Token {
+                    id: format!("hvs.{}", random_id().replace('-', "")),
+                    accessor: random_id(),
+                    policies,
+                    meta: req.body.get("meta").cloned(),
+                    display_name: format!(
+                        "token{}",
+                        req.str("display_name")
+                            .map(|n| format!("-{n}"))
+                            .unwrap_or_default()
+                    ),
+                    creation_time: unix_time(),
+                    ttl,
+                    explicit_max_ttl: req
+                        .str("explicit_max_ttl")
+                        .and_then(parse_ttl)
+                        .unwrap_or_default(),
+                    renewable: req.body.get("renewable").and_then(Value::as_bool) != Some(false),
+                    orphan,
+                    num_uses: req
+                        .body
+                        .get("num_uses")
+                        .and_then(Value::as_u64)
+                        .unwrap_or(0),
+                    path: format!("auth/token/{}", path[0]),
+                };
+                let auth = token.auth();
+                self.tokens.insert(token.id.clone(), token);
+                Response::auth(auth)
+            }
+            ("GET", ["lookup-self"]) => Response::data(self.tokens[current].lookup()),
+            ("POST", ["lookup"]) | ("GET", ["lookup"]) => {
+                match req.str("token").and_then(|t| self.tokens.get(t)) {
+                    Some(token) => Response::data(token.lookup()),
+                    None => Response::error(403, "bad token"),
+                }
+            }
+            ("POST", ["lookup-accessor"]) => match self.find_accessor(req.str("accessor")) {
+                Some(id) => {
+                    let mut data = self.tokens[&id].lookup();
+                    data["id"] = json!("");
+                    Response::data(data)
+                }
+                None => Response::error(400, "invalid accessor"),
+            },
+            ("POST", ["renew-self"]) => self.renew_token(current, req.str("increment")),
+            ("POST", ["renew"]) => match req.str("token") {
+                Some(token) if self.tokens.contains_key(token) => {
+                    self.renew_token(token, req.str("increment"))
+                }
+                _ => Response::error(400, "invalid token"),
+            },
+            ("POST", ["revoke-self"]) => {
+                self.tokens.remove(current);
+                Response::empty()
+            }
+            ("POST", ["revoke"]) | ("POST", ["revoke-orphan"]) => {
+                if let Some(token) = req.str("token") {
+                    self.tokens.remove(token);
+                }
+                Response::empty()
+            }
+            ("POST", ["revoke-accessor"]) => {
+                if let Some(id) = self.find_accessor(req.str("accessor")) {
+                    self.tokens.remove(&id);
+                }
+                Response::empty()
+            }
+            ("LIST", ["accessors"]) => Response::data(json!({
+                "keys": self.tokens.values().map(|t| &t.accessor).collect::<Vec<_>>(),
+            })),
+            _ => Response::unsupported(),
+        }
+    }
+
+    fn find_accessor(&self, accessor: Option<&str>) -> Option<String> {
+        let accessor = accessor?;
+        self.tokens
+            .values()
+            .find(|t| t.accessor == accessor)
+            .map(|t| t.id.clone())
+    }
+
+    fn renew_token(&mut self, id: &str, increment: Option<&str>) -> Response {
+        let token = self.tokens.get_mut(id).unwrap();
+        if !token.renewable {
+            return Response::error(400, "lease is not renewable");
+        }
+        let increment = increment.and_then(parse_ttl).unwrap_or(token.ttl);
+        let elapsed = unix_time() - token.creation_time;
+        token.ttl = elapsed + increment;
+        if token.explicit_max_ttl > 0 {
+            token.ttl = token.ttl.min(token.explicit_max_ttl);
+        }
+        let mut auth = token.auth();
+        auth["lease_duration"] = json!(token.remaining());
+        Response::auth(auth)
+    }
+
+    fn handle_mount(&mut self, req: &Request, token: &str) -> Response {
+        let full = req.path.join("/") + "/";
+        let Some(mount_path) = self
+            .mounts
+            .keys()
+            .filter(|m| full.starts_with(m.as_str()))
+            .max_by_key(|m| m.len())
+            .cloned()
+        else {
+            return Response::unsupported();
+        };
+        let rest = full[mount_path.len()..].trim_end_matches('/').to_string();
+        let rest: Vec<&str> = rest.split('/').filter(|s| !s.is_empty()).collect();
+
+        let mount = self.mounts.get_mut(&mount_path).unwrap();
+        match &mut mount.backend {
+            Backend::Kv1(secrets) => handle_kv1(secrets, req, &rest),
+            Backend::Cubbyhole(secrets) => {
+                handle_kv1(secrets.entry(token.to_string()).or_default(), req, &rest)
+            }
+            Backend::Kv2(store) => handle_kv2(store, req, &rest),
+            Backend::Transit(keys) => handle_transit(keys, req, &rest),
+        }
+    }
+}
+
+fn handle_kv1(secrets: &mut BTreeMap<String, Value>, req: &Request, path: &[&str]) -> Response {
+    let key = path.join("/");
+    match req.method.as_str() {
+        "GET" => match secrets.get(&key) {
+            Some(data) => Response::data(data.clone()),
+            None => Response::not_found(),
+        },
+        "POST" | "PUT" => {
+            if !req.body.is_object() {
+                return Response::error(400, "missing data fields");
+            }
+            secrets.insert(key, req.body.clone());
+            Response::empty()
+        }
+        "DELETE" => {
+            secrets.remove(&key);
+            Response::empty()
+        }
+        "LIST" => list_keys(secrets.keys(), &key),
+        _ => Response::error(405, "unsupported operation"),
+    }
+}
+
+fn handle_kv2(store: &mut Kv2Store, req: &Request, path: &[&str]) -> Response {
+    let (op, key) = match path {
+        [op, rest @ ..] => (*op, rest.join("/")),
+        [] => return Response::unsupported(),
+    };
+    match (req.method.as_str(), op) {
+        ("GET", "config") => Response::data(json!({
+            "cas_required": store.cas_required,
+            "delete_version_after": "0s",
+            "max_versions": store.max_versions,
+        })),
+        ("POST", "config") | ("PUT", "config") => {
+            if let Some(v) = req.body.get("max_versions").and_then(Value::as_u64) {
+                store.max_versions = v;
+            }
+            if let Some(v) = req.body.get("cas_required").and_then(Value::as_bool) {
+                store.cas_required = v;
+            }
+            Response::empty()
+        }
+        ("GET", "data") => {
+            let Some(secret) = store.secrets.get(&key) else {
+                return Response::not_found();
+            };
+            let version = req
+                .query
+                .get("version")
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(secret.current_version);
+            match secret.versions.get(&version) {
+                Some(v) if !v.destroyed && v.deletion_time.is_empty() => Response::data(json!({
+                    "data": v.data,
+                    "metadata": v.metadata(version, secret),
+                })),
+                _ => Response::not_found(),
+            }
+        }
+        ("POST", "data") | ("PUT", "data") => {
+            let Some(data) = req.body.get("data").filter(|d| d.is_object()) else {
+                return Response::error(400, "no data provided");
+            };
+            let secret = store.secrets.entry(key).or_default();
+            let cas = req.body.pointer("/options/cas").and_then(Value::as_u64);
+            match cas {
+                Some(cas) if cas != secret.current_version => {
+                    return Response::error(
+                        400,
+                        "check-and-set parameter did not match the current version",
+                    )
+                }
+                None if store.cas_required || secret.cas_required => {
+                    return Response::error(400, "check-and-set parameter required for this call")
+                }
+                _ => {}
+            }
+
+            let now = rfc3339(unix_time());
+            if secret.versions.is_empty() {
+                secret.created_time = now.clone();
+            }
+            secret.updated_time = now.clone();
+            secret.current_version += 1;
+            secret.versions.insert(
+                secret.current_version,
+                Kv2Version {
+                    data: data.clone(),
+                    created_time: now,
+                    deletion_time: String::new(),
+                    destroyed: false,
+                },
+            );
+            let max = match secret.max_versions {
+                0 => store.max_versions,
+                m => m,
+            };
+            let max = if max == 0 { 10 } else { max };
+            while secret.versions.len() as u64 > max {
+                let oldest = *secret.versions.keys().next().unwrap();
+                secret.versions.remove(&oldest);
+            }
+            let version = secret.current_version;
+            Response::data(secret.versions[&version].metadata(version, secret))
+        }
+        ("DELETE", "data") => {
+            if let Some(secret) = store.secrets.get_mut(&key) {
+                let current = secret.current_version;
+                if let Some(v) = secret.versions.get_mut(&current) {
+                    v.deletion_time = rfc3339(unix_time());
+                }
+            }
+            Response::empty()
+        }
+        ("POST", "delete") | ("POST", "undelete") | ("POST", "destroy") => {
+            let Some(secret) = store.secrets.get_mut(&key) else {
+                return Response::empty();
+            };
+            let versions: Vec<u64> = req
+                .body
+                .get("versions")
+                .and_then(Value::as_array)
+                .map(|v| v.iter().filter_map(Value::as_u64).collect())
+                .unwrap_or_default();
+            for version in versions {
+                if let Some(v) = secret.versions.get_mut(&version) {
+                    match op {
+                        "delete" => v.deletion_time = rfc3339(unix_time()),
+                        "undelete" => v.deletion_time = String::new(),
+                        _ => {
+                            v.destroyed = true;
+                            v.data = Value::Null;
+                        }
+                    }
+                }
+            }
+            Response::empty()
+        }
+        ("LIST", "metadata") => list_keys(store.secrets.keys(), &key),
+        ("GET", "metadata") => {
+            let Some(secret) = store.secrets.get(&key) else {
+                return Response::not_found();
+            };
+            let versions: Map<String, Value> = secret
+                .versions
+                .iter()
+                .map(|(n, v)| {
+                    (
+                        n.to_string(),
+                        json!({
+                            "created_time": v.created_time,
+                            "deletion_time": v.deletion_time,
+                            "destroyed": v.destroyed,
+                        }),
+                    )
+                })
+                .collect();
+            Response::data(json!({
+                "cas_required": secret.cas_required,
+                "created_time": secret.created_time,
+                "current_version": secret.current_version,
+                "delete_version_after": "0s",
+                "max_versions": secret.max_versions,
+                "oldest_version": secret.versions.keys().next().copied().unwrap_or_default(),
+                "updated_time": secret.updated_time,
+                "custom_metadata": secret.custom_metadata,
+                "versions": versions,
+            }))
+        }
+        ("POST", "metadata") | ("PUT", "metadata") => {
+            let secret = store.secrets.entry(key).or_default();
+            if let Some(v) = req.body.get("max_versions").and_then(Value::as_u64) {
+                secret.max_versions = v;
+            }
+            if let Some(v) = req.body.get("cas_required").and_then(Value::as_bool) {
+                secret.cas_required = v;
+            }
+            if let Some(v) = req.body.get("custom_metadata") {
+                secret.custom_metadata = Some(v.clone());
+            }
+            Response::empty()
+        }
+        ("DELETE", "metadata") => {
+            store.secrets.remove(&key);
+            Response::empty()
+        }
+        _ => Response::unsupported(),
+    }
+}
+
+fn handle_transit(
+    keys: &mut BTreeMap<String, TransitKey>,
+    req: &Request,
+    path: &[&str],
+) -> Response {
+    match (req.method.as_str(), path) {
+        ("LIST", ["keys"]) => list_keys(keys.keys(), ""),
+        ("POST", ["keys", name]) => {
+            let key_type = req.str("type").unwrap_or("aes256-gcm96");
+            keys.entry(name.to_string())
+                .or_insert_with(|| TransitKey::new(key_type));
+            Response::empty()
+        }
+        ("GET", ["keys", name]) => match keys.get(*name) {
+            Some(key) => Response::data(json!({
+                "type": key.key_type,
+                "deletion_allowed": key.deletion_allowed,
+                "derived": false,
+                "exportable": key.exportable,
+                "allow_plaintext_backup": key.allow_plaintext_backup,
+                "keys": key
+                    .versions
+                    .iter()
+                    .map(|(n, (time, _))| (n.to_string(), json!(time)))
+                    .collect::<Map<String, Value>>(),
+                "min_decryption_version": key.min_decryption_version,
+                "min_encryption_version": key.min_encryption_version,
+                "name": name,
+                "supports_encryption": true,
+                "supports_decryption": true,
+                "supports_derivation": true,
+                "supports_signing": false,
+                "imported": false,
+            })),
+            None => Response::not_found(),
+        },
+        ("DELETE", ["keys", name]) => match keys.get(*name) {
+            Some(key) if !key.deletion_allowed => {
+                Response::error(400, "deletion is not allowed for this key")
+            }
+            _ => {
+                keys.remove(*name);
+                Response::empty()
+            }
+        },
+        ("POST", ["keys", name, "config"]) => {
+            let Some(key) = keys.get_mut(*name) else {
+                return Response::error(400, "no existing key found");
+            };
+            if let Some(v) = req.body.get("deletion_allowed").and_then(Value::as_bool) {
+                key.deletion_allowed = v;
+            }
+            if let Some(v) = req.body.get("exportable").and_then(Value::as_bool) {
+                key.exportable = v;
+            }
+            if let Some(v) = req
+                .body
+                .get("allow_plaintext_backup")
+                .and_then(Value::as_bool)
+            {
+                key.allow_plaintext_backup = v;
+            }
+            if let Some(v) = req
+                .body
+                .get("min_decryption_version")
+                .and_then(Value::as_u64)
+            {
+                key.min_decryption_version = v;
+            }
+            if let Some(v) = req
+                .body
+                .get("min_encryption_version")
+                .and_then(Value::as_u64)
+            {
+                key.min_encryption_version = v;
+            }
+            Response::empty()
+        }
+        ("POST", ["keys", name, "rotate"]) => match keys.get_mut(*name) {
+            Some(key) => {
+                key.rotate();
+                Response::empty()
+            }
+            None => Response::error(400, "no existing key found"),
+        },
+        ("POST", ["encrypt", name]) => {
+            let Some(plaintext) = req.str("plaintext") else {
+                return Response::error(400, "missing plaintext to encrypt");
+            };
+            let key_type = req.str("type").unwrap_or("aes256-gcm96");
+            let key = keys
+                .entry(name.to_string())
+                .or_insert_with(|| TransitKey::new(key_type));
+            let version = match req.body.get("key_version").and_then(Value::as_u64) {
+                Some(v) if v > 0 => v,
+                _ => key.latest(),
+            };
+            let Some((_, material)) = key.versions.get(&version) else {
+                return Response::error(400, "invalid key version");
+            };
+            let ciphertext = format!(
+                "vault:v{}:{}",
+                version,
+                to_hex(&xor(plaintext.as_bytes(), material))
+            );
+            Response::data(json!({ "ciphertext": ciphertext, "key_version": version }))
+        }
+        ("POST", ["decrypt", name]) => {
+            let Some(key) = keys.get(*name) else {
+                return Response::error(400, "encryption key not found");
+            };
+            let parsed = req.str("ciphertext").and_then(|c| {
+                let rest = c.strip_prefix("vault:v")?;
+                let (version, data) = rest.split_once(':')?;
+                Some((version.parse::<u64>().ok()?, from_hex(data)?))
+            });
+            let Some((version, data)) = parsed else {
+                return Response::error(400, "invalid ciphertext: no prefix");
+            };
+            if version < key.min_decryption_version {
+                return Response::error(
+                    400,
+                    "ciphertext or signature version is disallowed by policy (too old)",
+                );
+            }
+            let Some((_, material)) = key.versions.get(&version) else {
+                return Response::error(400, "invalid key version");
+            };
+            match String::from_utf8(xor(&data, material)) {
+                Ok(plaintext) => Response::data(json!({ "plaintext": plaintext })),
+                Err(_) => Response::error(400, "cipher: message authentication failed"),
+            }
+        }
+        _ => Response::unsupported(),
+    }
+}
+
+/// Lists the keys directly under the given prefix, folding nested keys into
+/// their directory like Vault does.
+fn list_keys<'a>(keys: impl Iterator<Item = &'a String>, prefix: &str) -> Response {
+    let prefix = if prefix.is_empty() {
+        String::new()
+    } else {
+        format!("{}/", prefix.trim_end_matches('/'))
+    };
+    let mut found: Vec<String> = keys
+        .filter_map(|k| k.strip_prefix(prefix.as_str()))
+        .map(|k| match k.split_once('/') {
+            Some((dir, _)) => format!("{dir}/"),
+            None => k.to_string(),
+        })
+        .collect();
+    found.dedup();
+    if found.is_empty() {
+        return Response::not_found();
+    }
+    Response::data(json!({ "keys": found }))
+}
+
+fn mount_info(mount: &Mount) -> Value {
+    json!({
+        "accessor": mount.accessor,
+        "config": {
+            "default_lease_ttl": 0,
+            "force_no_cache": false,
+            "max_lease_ttl": 0,
+        },
+        "description": mount.description,
+        "external_entropy_access": false,
+        "local": false,
+        "options": mount.options,
+        "seal_wrap": false,
+        "type": mount.mount_type,
+        "uuid": mount.uuid,
+    })
+}
+
+fn mount_path(path: &[&str]) -> String {
+    format!("{}/", path.join("/"))
+}
+
+/// Parses a TTL given as a number of seconds or a duration like `1h`.
+fn parse_ttl(ttl: &str) -> Option<u64> {
+    let split = ttl.find(|c: char| !c.is_ascii_digit()).unwrap_or(ttl.len());
+    let (number, unit) = ttl.split_at(split);
+    let number: u64 = number.parse().ok()?;
+    match unit {
+        "" | "s" => Some(number),
+        "m" => Some(number * 60),
+        "h" => Some(number * 3600),
+        "d" => Some(number * 86400),
+        _ => None,
+    }
+}
+
+fn decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => match u8::from_str_radix(&value[i + 1..i + 3], 16) {
+                Ok(b) => {
+                    out.push(b);
+                    i += 3;
+                    continue;
+                }
+                Err(_) => out.push(b'%'),
+            },
+            b'+' => out.push(b' '),
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn xor(data: &[u8], key: &[u8]) -> Vec<u8> {
+    data.iter()
+        .zip(key.iter().cycle())
+        .map(|(d, k)| d ^ k)
+        .collect()
+}
+
+fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(data: &str) -> Option<Vec<u8>> {
+    if !data.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..data.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(data.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn random_u64() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+fn random_id() -> String {
+    let a = random_u64();
+    let b = random_u64();
+    format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        a >> 32,
+        (a >> 16) & 0xffff,
+        a & 0xffff,
+        b >> 48,
+        b & 0xffff_ffff_ffff
+    )
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Formats a unix timestamp as an RFC 3339 UTC timestamp.
+fn rfc3339(secs: u64) -> String {
+    let days = (secs / 86400) as i64;
+    let rem = secs % 86400;
+
+    // Converts days since the epoch to a civil date, see
+    // <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        (rem % 3600) / 60,
+        rem % 60
+    )
+}
@@ -0,0 +1,381 @@
+//! Keeps the leases of dynamic secrets alive in the background.
+//!
+//! A [LeaseManager] holds the leases registered with it and renews each of
+//! them once a configurable fraction of its TTL has elapsed. When a lease can
+//! no longer be renewed (because it isn't renewable or because it reached its
+//! max TTL) subscribers are notified through a [LeaseEvent::Expiring] event so
+//! that new credentials can be fetched before the current ones expire. All
+//! leases still held are revoked when the manager is shut down.
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use tokio::sync::RwLock;
+//! use vaultrs::client::{VaultClient, VaultClientSettingsBuilder};
+//! use vaultrs::database;
+//! use vaultrs::lease_manager::{LeaseEvent, LeaseManager};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let client = VaultClient::new(
+//!     VaultClientSettingsBuilder::default()
+//!         .address("https://127.0.0.1:8200")
+//!         .token("TOKEN")
+//!         .build()
+//!         .unwrap()
+//! ).unwrap();
+//! let client = Arc::new(RwLock::new(client));
+//!
+//! let manager = LeaseManager::new(client.clone()).start();
+//! let mut events = manager.subscribe();
+//!
+//! let creds = database::role::creds_with_lease(&*client.read().await, "database", "my-role")
+//!     .await
+//!     .unwrap();
+//! manager.register(&creds);
+//!
+//! while let Ok(event) = events.recv().await {
+//!     if let LeaseEvent::Expiring { .. } = event {
+//!         // Fetch new credentials here
+//!         break;
+//!     }
+//! }
+//!
+//! // Revokes the database credentials
+//! manager.shutdown().await;
+//! # }
+//! ```
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use tokio::{
+    sync::{broadcast, mpsc, oneshot, RwLock},
+    task::JoinHandle,
+    time::{sleep_until, Instant},
+};
+
+use crate::{
+    api::{sys::requests::RenewLeaseRequest, Secret},
+    client::{parse_duration, Client},
+    error::ApiErrorKind,
+    sys::lease,
+};
+
+/// Settings which control when a [LeaseManager] renews its leases.
+#[derive(Clone, Debug)]
+pub struct LeaseManagerConfig {
+    /// The fraction of the remaining TTL after which a renewal is attempted.
+    /// Defaults to two thirds.
+    pub renew_fraction: f64,
+    /// The increment requested when renewing a lease. Defaults to the
+    /// server-side default.
+    pub increment: Option<String>,
+    /// The delay between attempts after a renewal failed. Defaults to 10
+    /// seconds.
+    pub retry_delay: Duration,
+    /// The number of events buffered for subscribers which fall behind.
+    /// Defaults to 64.
+    pub capacity: usize,
+}
+
+impl Default for LeaseManagerConfig {
+    fn default() -> Self {
+        LeaseManagerConfig {
+            renew_fraction: 2.0 / 3.0,
+            increment: None,
+            retry_delay: Duration::from_secs(10),
+            capacity: 64,
+        }
+    }
+}
+
+/// Events emitted by a running [LeaseManager].
+#[derive(Clone, Debug)]
+pub enum LeaseEvent {
+    /// The lease was renewed for the given number of seconds.
+    Renewed {
+        lease_id: String,
+        lease_duration: u64,
+    },
+    /// The lease can no longer be renewed, either because it isn't renewable
+    /// or because it reached its max TTL. New credentials should be fetched
+    /// before it expires.
+    Expiring { lease_id: String },
+    /// The lease expired, or Vault rejected its renewal because it doesn't
+    /// exist anymore, and it is no longer held by the manager.
+    Expired { lease_id: String },
+    /// The lease was revoked when the manager was shut down.
+    Revoked { lease_id: String },
+}
+
+/// Renews the leases registered with it and revokes them on shutdown.
+///
+/// The client is shared behind a [RwLock] so that it can be used together with
+/// a task which replaces its token, such as a token lifetime watcher.
+pub struct LeaseManager<C: Client> {
+    client: Arc<RwLock<C>>,
+    config: LeaseManagerConfig,
+}
+
+impl<C: Client + 'static> LeaseManager<C> {
+    /// Creates a new [LeaseManager] using the default [LeaseManagerConfig].
+    pub fn new(client: Arc<RwLock<C>>) -> Self {
+        LeaseManager {
+            client,
+            config: LeaseManagerConfig::default(),
+        }
+    }
+
+    /// Sets the [LeaseManagerConfig] used by this manager.
+    pub fn with_config(mut self, config: LeaseManagerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Starts managing leases in a background task.
+    pub fn start(self) -> LeaseManagerHandle {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        let (events, _) = broadcast::channel(self.config.capacity.max(1));
+        let task = tokio::spawn(self.run(receiver, events.clone()));
+        LeaseManagerHandle {
+            commands,
+            events,
+            task,
+        }
+    }
+
+    async fn run(
+        self,
+        mut commands: mpsc::UnboundedReceiver<Command>,
+        events: broadcast::Sender<LeaseEvent>,
+    ) {
+        let mut leases: HashMap<String, Lease> = HashMap::new();
+        loop {
+            let next = leases.values().map(|l| l.next_at).min();
+            let command = tokio::select! {
+                command = commands.recv() => command,
+                _ = sleep_until(next.unwrap_or_else(Instant::now)), if next.is_some() => {
+                    self.process(&mut leases, &events).await;
+                    continue;
+                }
+            };
+
+            match command {
+                Some(Command::Register(lease_id, granted, renewable)) => {
+                    debug!("Managing lease {}", lease_id);
+                    leases.insert(lease_id, Lease::new(granted, renewable, &self.config));
+                }
+                Some(Command::Deregister(lease_id)) => {
+                    debug!("No longer managing lease {}", lease_id);
+                    leases.remove(&lease_id);
+                }
+                Some(Command::Shutdown(done)) => {
+                    self.revoke_all(leases, &events).await;
+                    let _ = done.send(());
+                    return;
+                }
+                None => return,
+            }
+        }
+    }
+
+    /// Handles all leases which are due for a renewal or have expired.
+    async fn process(
+        &self,
+        leases: &mut HashMap<String, Lease>,
+        events: &broadcast::Sender<LeaseEvent>,
+    ) {
+        let now = Instant::now();
+        let due: Vec<String> = leases
+            .iter()
+            .filter(|(_, l)| l.next_at <= now)
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        for lease_id in due {
+            let state = leases.get(&lease_id).unwrap().state;
+            match state {
+                LeaseState::Renewable => self.renew(&lease_id, leases, events).await,
+                LeaseState::Capped => {
+                    let lease = leases.get_mut(&lease_id).unwrap();
+                    lease.state = LeaseState::Expiring;
+                    lease.next_at = lease.expires_at;
+                    let _ = events.send(LeaseEvent::Expiring { lease_id });
+                }
+                LeaseState::Expiring => {
+                    leases.remove(&lease_id);
+                    let _ = events.send(LeaseEvent::Expired { lease_id });
+                }
+            }
+        }
+    }
+
+    async fn renew(
+        &self,
+        lease_id: &str,
+        leases: &mut HashMap<String, Lease>,
+        events: &broadcast::Sender<LeaseEvent>,
+    ) {
+        let mut opts = RenewLeaseRequest::builder();
+        if let Some(increment) = &self.config.increment {
+            opts.increment(increment.as_str());
+        }
+        let result = lease::renew(&*self.client.read().await, lease_id, Some(&mut opts)).await;
+
+        let lease = leases.get_mut(lease_id).unwrap();
+        let now = Instant::now();
+        match result {
+            Ok(res) => {
+                let granted = Duration::from_secs(res.lease_duration);
+                // Vault silently caps the TTL once the max TTL is near, so a
+                // lease shorter than requested means we reached it. Without an
+                // increment the lease is renewed for its initial duration.
+                let requested = self
+                    .config
+                    .increment
+                    .as_deref()
+                    .and_then(parse_duration)
+                    .unwrap_or(lease.granted);
+                let capped = granted.is_zero() || granted < requested;
+                *lease = Lease::new(granted, res.renewable && !capped, &self.config);
+                let _ = events.send(LeaseEvent::Renewed {
+                    lease_id: lease_id.to_string(),
+                    lease_duration: res.lease_duration,
+                });
+                // The lease won't be renewed again, so subscribers are warned
+                // right away rather than after another fraction of its TTL.
+                if let LeaseState::Capped = lease.state {
+                    lease.state = LeaseState::Expiring;
+                    lease.next_at = lease.expires_at;
+                    let _ = events.send(LeaseEvent::Expiring {
+                        lease_id: lease_id.to_string(),
+                    });
+                }
+            }
+            Err(e) => {
+                warn!("Failed renewing lease {}: {}", lease_id, e);
+                let remaining = lease.expires_at.saturating_duration_since(now);
+                // Retrying is pointless once Vault rejected the lease itself,
+                // for example because it was revoked in the meantime.
+                let permanent = matches!(
+                    e.kind(),
+                    Some(
+                        ApiErrorKind::NotFound
+                            | ApiErrorKind::InvalidRequest
+                            | ApiErrorKind::LeaseExpired
+                    )
+                );
+                if permanent || remaining.is_zero() {
+                    leases.remove(lease_id);
+                    let _ = events.send(LeaseEvent::Expired {
+                        lease_id: lease_id.to_string(),
+                    });
+                } else {
+                    lease.next_at = now + self.config.retry_delay.min(remaining);
+                }
+            }
+        }
+    }
+
+    async fn revoke_all(
+        &self,
+        leases: HashMap<String, Lease>,
+        events: &broadcast::Sender<LeaseEvent>,
+    ) {
+        let client = self.client.read().await;
+        for lease_id in leases.into_keys() {
+            match lease::revoke(&*client, lease_id.as_str(), None).await {
+                Ok(_) => {
+                    let _ = events.send(LeaseEvent::Revoked { lease_id });
+                }
+                Err(e) => warn!("Failed revoking lease {}: {}", lease_id, e),
+            }
+        }
+    }
+}
+
+/// A handle to a running [LeaseManager].
+///
+/// Dropping the handle stops the manager without revoking the leases it
+/// holds, use [LeaseManagerHandle::shutdown] to revoke them.
+pub struct LeaseManagerHandle {
+    commands: mpsc::UnboundedSender<Command>,
+    events: broadcast::Sender<LeaseEvent>,
+    task: JoinHandle<()>,
+}
+
+impl LeaseManagerHandle {
+    /// Registers the lease of the given secret with the manager.
+    ///
+    /// Secrets which aren't attached to a lease are ignored.
+    pub fn register<T>(&self, secret: &Secret<T>) {
+        if secret.lease_id.is_empty() {
+            debug!("Secret has no lease, not registering it");
+            return;
+        }
+        let _ = self.commands.send(Command::Register(
+            secret.lease_id.clone(),
+            Duration::from_secs(secret.lease_duration),
+            secret.renewable,
+        ));
+    }
+
+    /// Stops managing the given lease without revoking it.
+    pub fn deregister(&self, lease_id: &str) {
+        let _ = self
+            .commands
+            .send(Command::Deregister(lease_id.to_string()));
+    }
+
+    /// Returns a receiver for the events emitted by the manager from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<LeaseEvent> {
+        self.events.subscribe()
+    }
+
+    /// Revokes all leases held by the manager and waits for it to finish.
+    pub async fn shutdown(self) {
+        let (done, wait) = oneshot::channel();
+        if self.commands.send(Command::Shutdown(done)).is_ok() {
+            let _ = wait.await;
+        }
+        let _ = self.task.await;
+    }
+}
+
+enum Command {
+    Register(String, Duration, bool),
+    Deregister(String),
+    Shutdown(oneshot::Sender<()>),
+}
+
+#[derive(Clone, Copy)]
+enum LeaseState {
+    /// The lease will be renewed at `next_at`.
+    Renewable,
+    /// The lease can't be renewed, subscribers are notified at `next_at`.
+    Capped,
+    /// Subscribers were notified, the lease expires at `next_at`.
+    Expiring,
+}
+
+struct Lease {
+    granted: Duration,
+    expires_at: Instant,
+    next_at: Instant,
+    state: LeaseState,
+}
+
+impl Lease {
+    fn new(granted: Duration, renewable: bool, config: &LeaseManagerConfig) -> Self {
+        let now = Instant::now();
+        Lease {
+            granted,
+            expires_at: now + granted,
+            next_at: now + granted.mul_f64(config.renew_fraction),
+            state: if renewable {
+                LeaseState::Renewable
+            } else {
+                LeaseState::Capped
+            },
+        }
+    }
+}
@@ -214,6 +214,10 @@
 //! All requests implement the ability to be
 //! [wrapped](https://developer.hashicorp.com/vault/docs/concepts/response-wrapping). These
 //! can be passed in your application internally before being unwrapped.
+//! Wrapped responses are valid for the `wrap_ttl` configured on the client
+//! (10 minutes by default) unless a TTL is given with `wrap_with_ttl`.
+//! Unwrapping verifies that the token was created by the endpoint which was
+//! wrapped.
 //!
 //! ```no_run
 //! use vaultrs::api::ResponseWrapper;
@@ -272,6 +276,89 @@
 //! # }
 //! ```
 //!
+//! ### Retries
+//!
+//! Requests failing with a transient error (like a `503` during a leader
+//! election or a `429` from a rate limit quota) can be retried with an
+//! exponential backoff by configuring a `RetryPolicy`. The number of retries
+//! defaults to the `VAULT_MAX_RETRIES` environment variable, or two if it isn't
+//! set. Use `RetryPolicy::none()` to disable retries.
+//!
+//! ```no_run
+//! use vaultrs::client::{VaultClientSettingsBuilder, VaultClient};
+//! use vaultrs::client::retry::RetryPolicy;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let settings = VaultClientSettingsBuilder::default()
+//!     .address("https://127.0.0.1:8200")
+//!     .token("TOKEN")
+//!     .retry(RetryPolicy::builder().max_retries(3u32).build().unwrap())
+//!     .build()
+//!     .unwrap();
+//!
+//! let client = VaultClient::new(settings).unwrap();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ### High availability
+//!
+//! The addresses of every node of a cluster can be given with
+//! `VaultClientSettingsBuilder::addresses`. Requests are then sent to the
+//! active node, failing over to another node when it can't be reached or is
+//! sealed. Reads can be routed to performance standbys by configuring the
+//! [Failover][crate::client::failover::Failover] of the settings. A
+//! [Consistency][crate::client::consistency::Consistency] tracker makes the
+//! reads sent to standbys see the writes made before them.
+//!
+//! ### Middleware
+//!
+//! Custom [Middleware][crate::client::middleware::Middleware]s can be added to
+//! the client settings to modify every request before it's sent (e.g. to add
+//! headers or sign requests) and to inspect every successful response. They
+//! run in the order they were added.
+//!
+//! ### Namespaces
+//!
+//! The namespace, token, wrap TTL and headers of a subset of requests can be
+//! overridden through a [ScopedClient][crate::client::scoped::ScopedClient]
+//! returned by `Client::with_namespace` or `Client::scoped`. It shares the
+//! connection pool of the client it was created from.
+//!
+//! ### Leases
+//!
+//! Dynamic secrets can be fetched together with their lease using the
+//! `*_with_lease` variants of their functions. Enabling the `lease-manager`
+//! feature provides a [LeaseManager][crate::lease_manager::LeaseManager], which
+//! keeps the registered leases renewed in the background, notifies subscribers
+//! once a lease can no longer be renewed and revokes the leases it holds when
+//! shut down.
+//!
+//! ### Unsealing
+//!
+//! An [Unsealer][crate::unsealer::Unsealer] submits key shares, read from
+//! files, the environment or prompted from operators, to every sealed node of
+//! a cluster until they're unsealed and reports the progress of each node.
+//!
+//! ### Blocking
+//!
+//! Enabling the `blocking` feature provides a synchronous
+//! [VaultClient][crate::blocking::VaultClient] and blocking versions of the
+//! functions of the KV, PKI, sys, token and transit modules under
+//! [blocking][crate::blocking], for programs which don't run an async runtime.
+//!
+//! ### Testing
+//!
+//! Enabling the `fake` feature provides a [FakeVault][crate::fake::FakeVault]
+//! which serves an in-memory emulation of the most common engines, allowing
+//! code using this crate to be tested without running a Vault server.
+//!
+//! Interactions with a real server can also be recorded to a fixture file and
+//! replayed later by configuring a
+//! [Recording][crate::client::recording::Recording] in the client settings.
+//! Tokens and secret values are redacted from the recorded fixtures.
+//!
 //! //!
 //! [Hashicorp Vault]: https://developer.hashicorp.com/vault
 //! [aws tests]: https://github.com/jmgilman/vaultrs/blob/master/vaultrs-tests/tests/api_tests/aws.rs
@@ -285,15 +372,23 @@ extern crate tracing;
 pub mod api;
 pub mod auth;
 pub mod aws;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod client;
 pub mod cubbyhole;
 pub mod database;
 pub mod error;
+#[cfg(feature = "fake")]
+pub mod fake;
 pub mod identity;
 pub mod kv1;
 pub mod kv2;
+#[cfg(feature = "lease-manager")]
+pub mod lease_manager;
+pub mod logical;
 pub mod pki;
 pub mod ssh;
 pub mod sys;
 pub mod token;
 pub mod transit;
+pub mod unsealer;
@@ -0,0 +1,183 @@
+//! Executes requests against arbitrary paths.
+//!
+//! These functions can be used to interact with endpoints which aren't
+//! modeled by this crate, like the ones added by plugins or by newer versions
+//! of Vault. The complete [EndpointResult] returned by the server is returned
+//! with its `data` field parsed as the requested type, use
+//! [Value][serde_json::Value] when its structure isn't known.
+//!
+//! Requests are executed with the same token, namespace and retry policy as
+//! any other request made by the client. They can also be wrapped by passing
+//! a [LogicalRequest] to [wrap][crate::api::wrap].
+
+use rustify::enums::RequestMethod;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    api::{self, logical::requests::LogicalRequest, EndpointResult},
+    client::Client,
+    error::ClientError,
+};
+
+/// Provides access to the functions of this module through a [Client].
+///
+/// See [Client::logical]
+pub struct Logical<'a, C: Client> {
+    client: &'a C,
+}
+
+impl<'a, C: Client> Logical<'a, C> {
+    /// Creates an interface executing requests through the given client.
+    ///
+    /// See [Client::logical]
+    pub fn new(client: &'a C) -> Self {
+        Logical { client }
+    }
+
+    /// Reads the given path.
+    ///
+    /// See [read]
+    pub async fn read<T: DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<EndpointResult<T>, ClientError> {
+        read(self.client, path).await
+    }
+
+    /// Writes the given data to the given path.
+    ///
+    /// See [write]
+    pub async fn write<T: DeserializeOwned, D: Serialize>(
+        &self,
+        path: &str,
+        data: &D,
+    ) -> Result<Option<EndpointResult<T>>, ClientError> {
+        write(self.client, path, data).await
+    }
+
+    /// Patches the given path with the given data.
+    ///
+    /// See [patch]
+    pub async fn patch<T: DeserializeOwned, D: Serialize>(
+        &self,
+        path: &str,
+        data: &D,
+    ) -> Result<Option<EndpointResult<T>>, ClientError> {
+        patch(self.client, path, data).await
+    }
+
+    /// Lists the keys under the given path.
+    ///
+    /// See [list]
+    pub async fn list<T: DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<EndpointResult<T>, ClientError> {
+        list(self.client, path).await
+    }
+
+    /// Deletes the given path.
+    ///
+    /// See [delete]
+    pub async fn delete<T: DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<Option<EndpointResult<T>>, ClientError> {
+        delete(self.client, path).await
+    }
+}
+
+/// Reads the given path.
+///
+/// See [LogicalRequest]
+#[instrument(skip(client), err)]
+pub async fn read<T: DeserializeOwned>(
+    client: &impl Client,
+    path: &str,
+) -> Result<EndpointResult<T>, ClientError> {
+    let endpoint = LogicalRequest::builder()
+        .path(path)
+        .method(RequestMethod::GET)
+        .build()
+        .unwrap();
+    api::exec_with_envelope(client, endpoint)
+        .await?
+        .ok_or(ClientError::ResponseEmptyError)
+}
+
+/// Writes the given data to the given path. Returns [None] if the server
+/// responded without a body.
+///
+/// See [LogicalRequest]
+#[instrument(skip(client, data), err)]
+pub async fn write<T: DeserializeOwned, D: Serialize>(
+    client: &impl Client,
+    path: &str,
+    data: &D,
+) -> Result<Option<EndpointResult<T>>, ClientError> {
+    send(client, path, RequestMethod::POST, data).await
+}
+
+/// Patches the given path with the given data using a JSON merge patch.
+/// Returns [None] if the server responded without a body.
+///
+/// See [LogicalRequest]
+#[instrument(skip(client, data), err)]
+pub async fn patch<T: DeserializeOwned, D: Serialize>(
+    client: &impl Client,
+    path: &str,
+    data: &D,
+) -> Result<Option<EndpointResult<T>>, ClientError> {
+    send(client, path, RequestMethod::PATCH, data).await
+}
+
+/// Lists the keys under the given path.
+///
+/// See [LogicalRequest]
+#[instrument(skip(client), err)]
+pub async fn list<T: DeserializeOwned>(
+    client: &impl Client,
+    path: &str,
+) -> Result<EndpointResult<T>, ClientError> {
+    let endpoint = LogicalRequest::builder()
+        .path(path)
+        .method(RequestMethod::LIST)
+        .build()
+        .unwrap();
+    api::exec_with_envelope(client, endpoint)
+        .await?
+        .ok_or(ClientError::ResponseEmptyError)
+}
+
+/// Deletes the given path. Returns [None] if the server responded without a
+/// body.
+///
+/// See [LogicalRequest]
+#[instrument(skip(client), err)]
+pub async fn delete<T: DeserializeOwned>(
+    client: &impl Client,
+    path: &str,
+) -> Result<Option<EndpointResult<T>>, ClientError> {
+    let endpoint = LogicalRequest::builder()
+        .path(path)
+        .method(RequestMethod::DELETE)
+        .build()
+        .unwrap();
+    api::exec_with_envelope(client, endpoint).await
+}
+
+async fn send<T: DeserializeOwned, D: Serialize>(
+    client: &impl Client,
+    path: &str,
+    method: RequestMethod,
+    data: &D,
+) -> Result<Option<EndpointResult<T>>, ClientError> {
+    let data = serde_json::to_value(data).map_err(|e| ClientError::JsonParseError { source: e })?;
+    let endpoint = LogicalRequest::builder()
+        .path(path)
+        .method(method)
+        .data(data)
+        .build()
+        .unwrap();
+    api::exec_with_envelope(client, endpoint).await
+}
@@ -7,6 +7,7 @@ pub mod cert {
     use crate::api::pki::responses::{
         GenerateCertificateResponse, ReadCertificateResponse, RevokeCertificateResponse,
     };
+    use crate::api::Secret;
     use crate::client::Client;
     use crate::error::ClientError;
 
@@ -29,6 +30,27 @@ pub mod cert {
         api::exec_with_result(client, endpoint).await
     }
 
+    /// Generates a certificate using the given role and options, returning it
+    /// with its lease. The lease is only populated when the role is
+    /// configured with `generate_lease`.
+    ///
+    /// See [GenerateCertificateRequest]
+    pub async fn generate_with_lease(
+        client: &impl Client,
+        mount: &str,
+        role: &str,
+        opts: Option<&mut GenerateCertificateRequestBuilder>,
+    ) -> Result<Secret<GenerateCertificateResponse>, ClientError> {
+        let mut t = GenerateCertificateRequest::builder();
+        let endpoint = opts
+            .unwrap_or(&mut t)
+            .mount(mount)
+            .role(role)
+            .build()
+            .unwrap();
+        api::exec_with_lease(client, endpoint).await
+    }
+
     /// Lists all certificates
     ///
     /// See [ListCertificatesRequest]
@@ -1,6 +1,7 @@
 use crate::api;
 use crate::api::ssh::requests::{GenerateSSHCredsRequest, VerifySSHOTPRequest};
 use crate::api::ssh::responses::{GenerateSSHCredsResponse, VerifySSHOTPResponse};
+use crate::api::Secret;
 use crate::client::Client;
 use crate::error::ClientError;
 
@@ -25,6 +26,28 @@ pub async fn generate(
     .await
 }
 
+/// Generates SSH credentials for the given role, returning them with their
+/// lease
+///
+/// See [GenerateSSHCredsRequest]
+pub async fn generate_with_lease(
+    client: &impl Client,
+    mount: &str,
+    name: &str,
+    ip: &str,
+    username: Option<String>,
+) -> Result<Secret<GenerateSSHCredsResponse>, ClientError> {
+    let mut endpoint = GenerateSSHCredsRequest::builder();
+    if let Some(u) = username {
+        endpoint.username(u);
+    }
+    api::exec_with_lease(
+        client,
+        endpoint.mount(mount).name(name).ip(ip).build().unwrap(),
+    )
+    .await
+}
+
 /// Verify SSH OTP details
 ///
 /// See [VerifySSHOTPRequest]
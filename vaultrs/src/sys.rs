@@ -8,6 +8,7 @@ use crate::{
             },
             responses::{ReadHealthResponse, StartInitializationResponse, UnsealResponse},
         },
+        SecretString,
     },
     client::Client,
     error::ClientError,
@@ -70,7 +71,7 @@ pub async fn unseal(
     migrate: Option<bool>,
 ) -> Result<UnsealResponse, ClientError> {
     let endpoint = UnsealRequest::builder()
-        .key(key)
+        .key(key.map(SecretString::from))
         .reset(reset)
         .migrate(migrate)
         .build()
@@ -114,14 +115,217 @@ pub async fn status(client: &impl Client) -> Result<ServerStatus, ClientError> {
     }
 }
 
+pub mod rekey {
+    use crate::api;
+    use crate::api::sys::requests::{
+        CancelRekeyRequest, CancelRekeyVerificationRequest, DeleteRekeyBackupRequest,
+        ReadRekeyBackupRequest, ReadRekeyProgressRequest, ReadRekeyVerificationProgressRequest,
+        RekeyUpdateRequest, RekeyVerifyRequest, StartRekeyRequest, StartRekeyRequestBuilder,
+    };
+    use crate::api::sys::responses::{
+        RekeyBackupResponse, RekeyStatusResponse, RekeyUpdateResponse,
+        RekeyVerificationStatusResponse, RekeyVerifyResponse,
+    };
+    use crate::client::Client;
+    use crate::error::ClientError;
+
+    /// Reads the progress of the current rekey attempt.
+    ///
+    /// See [ReadRekeyProgressRequest]
+    pub async fn status(client: &impl Client) -> Result<RekeyStatusResponse, ClientError> {
+        let endpoint = ReadRekeyProgressRequest::builder().build().unwrap();
+        api::exec_with_no_result(client, endpoint).await
+    }
+
+    /// Starts a new rekey attempt.
+    ///
+    /// See [StartRekeyRequest]
+    pub async fn start(
+        client: &impl Client,
+        secret_shares: u64,
+        secret_threshold: u64,
+        opts: Option<&mut StartRekeyRequestBuilder>,
+    ) -> Result<RekeyStatusResponse, ClientError> {
+        let mut t = StartRekeyRequest::builder();
+        let endpoint = opts
+            .unwrap_or(&mut t)
+            .secret_shares(secret_shares)
+            .secret_threshold(secret_threshold)
+            .build()
+            .unwrap();
+        api::exec_with_no_result(client, endpoint).await
+    }
+
+    /// Cancels the current rekey attempt.
+    ///
+    /// See [CancelRekeyRequest]
+    pub async fn cancel(client: &impl Client) -> Result<(), ClientError> {
+        let endpoint = CancelRekeyRequest::builder().build().unwrap();
+        api::exec_with_empty(client, endpoint).await
+    }
+
+    /// Submits a single key share to the rekey attempt with the given nonce.
+    ///
+    /// See [RekeyUpdateRequest]
+    pub async fn update(
+        client: &impl Client,
+        key: &str,
+        nonce: &str,
+    ) -> Result<RekeyUpdateResponse, ClientError> {
+        let endpoint = RekeyUpdateRequest::builder()
+            .key(key)
+            .nonce(nonce)
+            .build()
+            .unwrap();
+        api::exec_with_no_result(client, endpoint).await
+    }
+
+    /// Reads the backup copy of the PGP-encrypted unseal keys.
+    ///
+    /// See [ReadRekeyBackupRequest]
+    pub async fn read_backup(client: &impl Client) -> Result<RekeyBackupResponse, ClientError> {
+        let endpoint = ReadRekeyBackupRequest::builder().build().unwrap();
+        api::exec_with_result(client, endpoint).await
+    }
+
+    /// Deletes the backup copy of the PGP-encrypted unseal keys.
+    ///
+    /// See [DeleteRekeyBackupRequest]
+    pub async fn delete_backup(client: &impl Client) -> Result<(), ClientError> {
+        let endpoint = DeleteRekeyBackupRequest::builder().build().unwrap();
+        api::exec_with_empty(client, endpoint).await
+    }
+
+    /// Reads the progress of the current rekey verification attempt.
+    ///
+    /// See [ReadRekeyVerificationProgressRequest]
+    pub async fn verification_status(
+        client: &impl Client,
+    ) -> Result<RekeyVerificationStatusResponse, ClientError> {
+        let endpoint = ReadRekeyVerificationProgressRequest::builder()
+            .build()
+            .unwrap();
+        api::exec_with_no_result(client, endpoint).await
+    }
+
+    /// Submits a single new key share to the rekey verification attempt with
+    /// the given nonce.
+    ///
+    /// See [RekeyVerifyRequest]
+    pub async fn verify(
+        client: &impl Client,
+        key: &str,
+        nonce: &str,
+    ) -> Result<RekeyVerifyResponse, ClientError> {
+        let endpoint = RekeyVerifyRequest::builder()
+            .key(key)
+            .nonce(nonce)
+            .build()
+            .unwrap();
+        api::exec_with_no_result(client, endpoint).await
+    }
+
+    /// Cancels the current rekey verification attempt and starts a new one.
+    ///
+    /// See [CancelRekeyVerificationRequest]
+    pub async fn cancel_verification(
+        client: &impl Client,
+    ) -> Result<RekeyVerificationStatusResponse, ClientError> {
+        let endpoint = CancelRekeyVerificationRequest::builder().build().unwrap();
+        api::exec_with_no_result(client, endpoint).await
+    }
+}
+
+pub mod generate_root {
+    use base64::engine::general_purpose::STANDARD_NO_PAD;
+    use base64::Engine;
+
+    use crate::api;
+    use crate::api::sys::requests::{
+        CancelGenerateRootRequest, GenerateRootUpdateRequest, ReadGenerateRootProgressRequest,
+        StartGenerateRootRequest, StartGenerateRootRequestBuilder,
+    };
+    use crate::api::sys::responses::GenerateRootStatusResponse;
+    use crate::api::SecretString;
+    use crate::client::Client;
+    use crate::error::ClientError;
+
+    /// Reads the progress of the current root generation attempt.
+    ///
+    /// See [ReadGenerateRootProgressRequest]
+    pub async fn status(client: &impl Client) -> Result<GenerateRootStatusResponse, ClientError> {
+        let endpoint = ReadGenerateRootProgressRequest::builder().build().unwrap();
+        api::exec_with_no_result(client, endpoint).await
+    }
+
+    /// Starts a new root generation attempt.
+    ///
+    /// Without a PGP key the response contains the OTP which, together with
+    /// [decode_token], decodes the generated token.
+    ///
+    /// See [StartGenerateRootRequest]
+    pub async fn start(
+        client: &impl Client,
+        opts: Option<&mut StartGenerateRootRequestBuilder>,
+    ) -> Result<GenerateRootStatusResponse, ClientError> {
+        let mut t = StartGenerateRootRequest::builder();
+        let endpoint = opts.unwrap_or(&mut t).build().unwrap();
+        api::exec_with_no_result(client, endpoint).await
+    }
+
+    /// Cancels the current root generation attempt.
+    ///
+    /// See [CancelGenerateRootRequest]
+    pub async fn cancel(client: &impl Client) -> Result<(), ClientError> {
+        let endpoint = CancelGenerateRootRequest::builder().build().unwrap();
+        api::exec_with_empty(client, endpoint).await
+    }
+
+    /// Submits a single key share to the root generation attempt with the
+    /// given nonce.
+    ///
+    /// See [GenerateRootUpdateRequest]
+    pub async fn update(
+        client: &impl Client,
+        key: &str,
+        nonce: &str,
+    ) -> Result<GenerateRootStatusResponse, ClientError> {
+        let endpoint = GenerateRootUpdateRequest::builder()
+            .key(key)
+            .nonce(nonce)
+            .build()
+            .unwrap();
+        api::exec_with_no_result(client, endpoint).await
+    }
+
+    /// Decodes the token of a completed root generation attempt with the OTP
+    /// returned when the attempt was started.
+    ///
+    /// The encoded token is the base64 encoding of the token XORed with the
+    /// OTP, so both have the same length.
+    pub fn decode_token(encoded_token: &str, otp: &str) -> Result<SecretString, ClientError> {
+        let mut token = STANDARD_NO_PAD
+            .decode(encoded_token.trim_end_matches('='))
+            .map_err(|_| ClientError::RootTokenDecodeError)?;
+        if token.len() != otp.len() {
+            return Err(ClientError::RootTokenDecodeError);
+        }
+        token.iter_mut().zip(otp.bytes()).for_each(|(t, o)| *t ^= o);
+        String::from_utf8(token)
+            .map(SecretString::from)
+            .map_err(|_| ClientError::RootTokenDecodeError)
+    }
+}
+
 pub mod auth {
     use std::collections::HashMap;
 
     use crate::api;
     use crate::api::sys::requests::{
         DisableAuthRequest, EnableAuthRequest, EnableAuthRequestBuilder, ListAuthsRequest,
+        ReadAuthTuneRequest, TuneAuthRequest, TuneAuthRequestBuilder,
     };
-    use crate::api::sys::responses::AuthResponse;
+    use crate::api::sys::responses::{AuthResponse, MountTuneResponse};
     use crate::client::Client;
     use crate::error::ClientError;
 
@@ -162,6 +366,118 @@ pub mod auth {
         let endpoint = ListAuthsRequest::builder().build().unwrap();
         api::exec_with_result(client, endpoint).await
     }
+
+    /// Reads the tuning configuration of the auth method at the given path
+    ///
+    /// See [ReadAuthTuneRequest]
+    #[instrument(skip(client), err)]
+    pub async fn read_tune(
+        client: &impl Client,
+        path: &str,
+    ) -> Result<MountTuneResponse, ClientError> {
+        let endpoint = ReadAuthTuneRequest::builder().path(path).build().unwrap();
+        api::exec_with_result(client, endpoint).await
+    }
+
+    /// Tunes the configuration of the auth method at the given path
+    ///
+    /// `sudo` required - This endpoint requires `sudo` capability in
+    ///  addition to any path-specific capabilities.
+    ///
+    /// See [TuneAuthRequest]
+    #[instrument(skip(client, opts), err)]
+    pub async fn tune(
+        client: &impl Client,
+        path: &str,
+        opts: Option<&mut TuneAuthRequestBuilder>,
+    ) -> Result<(), ClientError> {
+        let mut t = TuneAuthRequest::builder();
+        let endpoint = opts.unwrap_or(&mut t).path(path).build().unwrap();
+        api::exec_with_empty(client, endpoint).await
+    }
+}
+
+pub mod audit {
+    use std::collections::HashMap;
+
+    use crate::api;
+    use crate::api::sys::requests::{
+        AuditDevice, AuditHashRequest, DisableAuditDeviceRequest, EnableAuditDeviceRequest,
+        EnableAuditDeviceRequestBuilder, ListAuditDevicesRequest,
+    };
+    use crate::api::sys::responses::{AuditDeviceResponse, AuditHashResponse};
+    use crate::client::Client;
+    use crate::error::ClientError;
+
+    /// Enables an audit device at the given path
+    ///
+    /// `sudo` required - This endpoint requires `sudo` capability in
+    ///  addition to any path-specific capabilities.
+    ///
+    /// See [EnableAuditDeviceRequest]
+    #[instrument(skip(client, opts), err)]
+    pub async fn enable(
+        client: &impl Client,
+        path: &str,
+        device: AuditDevice,
+        opts: Option<&mut EnableAuditDeviceRequestBuilder>,
+    ) -> Result<(), ClientError> {
+        let mut t = EnableAuditDeviceRequest::builder();
+        let endpoint = opts
+            .unwrap_or(&mut t)
+            .path(path)
+            .device(device)
+            .build()
+            .unwrap();
+        api::exec_with_empty(client, endpoint).await
+    }
+
+    /// Disables the audit device at the given path.
+    ///
+    /// `sudo` required - This endpoint requires `sudo` capability in
+    ///  addition to any path-specific capabilities.
+    ///
+    /// See [DisableAuditDeviceRequest]
+    #[instrument(skip(client), err)]
+    pub async fn disable(client: &impl Client, path: &str) -> Result<(), ClientError> {
+        let endpoint = DisableAuditDeviceRequest::builder()
+            .path(path)
+            .build()
+            .unwrap();
+        api::exec_with_empty(client, endpoint).await
+    }
+
+    /// Lists the enabled audit devices
+    ///
+    /// `sudo` required - This endpoint requires `sudo` capability in
+    ///  addition to any path-specific capabilities.
+    ///
+    /// See [ListAuditDevicesRequest]
+    #[instrument(skip(client), err)]
+    pub async fn list(
+        client: &impl Client,
+    ) -> Result<HashMap<String, AuditDeviceResponse>, ClientError> {
+        let endpoint = ListAuditDevicesRequest::builder().build().unwrap();
+        api::exec_with_result(client, endpoint).await
+    }
+
+    /// Hashes the given input with the hash function and salt of the audit
+    /// device at the given path, as it would appear in its log.
+    ///
+    /// See [AuditHashRequest]
+    #[instrument(skip(client, input), err)]
+    pub async fn hash(
+        client: &impl Client,
+        path: &str,
+        input: &str,
+    ) -> Result<AuditHashResponse, ClientError> {
+        let endpoint = AuditHashRequest::builder()
+            .path(path)
+            .input(input)
+            .build()
+            .unwrap();
+        api::exec_with_result(client, endpoint).await
+    }
 }
 
 pub mod mount {
@@ -170,9 +486,12 @@ pub mod mount {
     use crate::api;
     use crate::api::sys::requests::{
         DisableEngineRequest, EnableEngineRequest, EnableEngineRequestBuilder,
-        GetConfigurationOfTheSecretEngineRequest, ListMountsRequest,
+        GetConfigurationOfTheSecretEngineRequest, ListMountsRequest, ReadMountTuneRequest,
+        TuneMountRequest, TuneMountRequestBuilder,
+    };
+    use crate::api::sys::responses::{
+        GetConfigurationOfTheSecretEngineResponse, MountResponse, MountTuneResponse,
     };
-    use crate::api::sys::responses::{GetConfigurationOfTheSecretEngineResponse, MountResponse};
     use crate::client::Client;
     use crate::error::ClientError;
 
@@ -226,6 +545,32 @@ pub mod mount {
         let endpoint = ListMountsRequest::builder().build().unwrap();
         api::exec_with_result(client, endpoint).await
     }
+
+    /// Reads the tuning configuration of the secret engine at the given path
+    ///
+    /// See [ReadMountTuneRequest]
+    #[instrument(skip(client), err)]
+    pub async fn read_tune(
+        client: &impl Client,
+        path: &str,
+    ) -> Result<MountTuneResponse, ClientError> {
+        let endpoint = ReadMountTuneRequest::builder().path(path).build().unwrap();
+        api::exec_with_result(client, endpoint).await
+    }
+
+    /// Tunes the configuration of the secret engine at the given path
+    ///
+    /// See [TuneMountRequest]
+    #[instrument(skip(client, opts), err)]
+    pub async fn tune(
+        client: &impl Client,
+        path: &str,
+        opts: Option<&mut TuneMountRequestBuilder>,
+    ) -> Result<(), ClientError> {
+        let mut t = TuneMountRequest::builder();
+        let endpoint = opts.unwrap_or(&mut t).path(path).build().unwrap();
+        api::exec_with_empty(client, endpoint).await
+    }
 }
 
 pub mod remount {
@@ -323,16 +668,84 @@ pub mod policy {
     }
 }
 
+pub mod capabilities {
+    use std::collections::HashMap;
+
+    use crate::api;
+    use crate::api::sys::requests::{
+        CapabilitiesAccessorRequest, CapabilitiesRequest, CapabilitiesSelfRequest,
+    };
+    use crate::api::sys::Capability;
+    use crate::client::Client;
+    use crate::error::ClientError;
+
+    /// Returns the capabilities of the given token on each of the given paths.
+    ///
+    /// See [CapabilitiesRequest]
+    #[instrument(skip(client, token), err)]
+    pub async fn token(
+        client: &impl Client,
+        token: &str,
+        paths: &[&str],
+    ) -> Result<HashMap<String, Vec<Capability>>, ClientError> {
+        let endpoint = CapabilitiesRequest::builder()
+            .token(token)
+            .paths(to_vec(paths))
+            .build()
+            .unwrap();
+        Ok(api::exec_with_result(client, endpoint).await?.paths)
+    }
+
+    /// Returns the capabilities of the client token on each of the given
+    /// paths.
+    ///
+    /// See [CapabilitiesSelfRequest]
+    #[instrument(skip(client), err)]
+    pub async fn token_self(
+        client: &impl Client,
+        paths: &[&str],
+    ) -> Result<HashMap<String, Vec<Capability>>, ClientError> {
+        let endpoint = CapabilitiesSelfRequest::builder()
+            .paths(to_vec(paths))
+            .build()
+            .unwrap();
+        Ok(api::exec_with_result(client, endpoint).await?.paths)
+    }
+
+    /// Returns the capabilities of the token with the given accessor on each
+    /// of the given paths.
+    ///
+    /// See [CapabilitiesAccessorRequest]
+    #[instrument(skip(client), err)]
+    pub async fn accessor(
+        client: &impl Client,
+        accessor: &str,
+        paths: &[&str],
+    ) -> Result<HashMap<String, Vec<Capability>>, ClientError> {
+        let endpoint = CapabilitiesAccessorRequest::builder()
+            .accessor(accessor)
+            .paths(to_vec(paths))
+            .build()
+            .unwrap();
+        Ok(api::exec_with_result(client, endpoint).await?.paths)
+    }
+
+    fn to_vec(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+}
+
 pub mod wrapping {
-    use serde::de::DeserializeOwned;
+    use serde::{de::DeserializeOwned, Serialize};
 
     use crate::{
         api::{
             self,
             sys::{
-                requests::{UnwrapRequest, WrappingLookupRequest},
+                requests::{RewrapRequest, UnwrapRequest, WrapRequest, WrappingLookupRequest},
                 responses::WrappingLookupResponse,
             },
+            WrapInfo,
         },
         client::Client,
         error::ClientError,
@@ -360,11 +773,37 @@ pub mod wrapping {
         token: Option<&str>,
     ) -> Result<D, ClientError> {
         let endpoint = UnwrapRequest {
-            token: token.map(|v| v.to_string()),
+            token: token.map(|v| v.into()),
         };
         let res = api::exec_with_result(client, endpoint).await?;
         serde_json::value::from_value(res).map_err(|e| ClientError::JsonParseError { source: e })
     }
+
+    /// Wraps the given data in a token wrapped response. The token is valid
+    /// for the given TTL, or the `wrap_ttl` configured on the client if none
+    /// is given.
+    ///
+    /// See [WrapRequest]
+    pub async fn wrap<T: Serialize>(
+        client: &impl Client,
+        data: &T,
+        ttl: Option<&str>,
+    ) -> Result<WrapInfo, ClientError> {
+        let data =
+            serde_json::to_vec(data).map_err(|e| ClientError::JsonParseError { source: e })?;
+        let endpoint = WrapRequest::builder().data(data).build().unwrap();
+        let ttl = ttl.unwrap_or(client.settings().wrap_ttl.as_str());
+        Ok(api::wrap_with_ttl(client, endpoint, ttl).await?.info)
+    }
+
+    /// Rewraps a token wrapped response, returning a new token for the same
+    /// response. The original token is no longer valid afterwards.
+    ///
+    /// See [RewrapRequest]
+    pub async fn rewrap(client: &impl Client, token: &str) -> Result<WrapInfo, ClientError> {
+        let endpoint = RewrapRequest::builder().token(token).build().unwrap();
+        api::exec_with_wrap_info(client, endpoint).await
+    }
 }
 
 pub mod tools {
@@ -393,3 +832,114 @@ pub mod tools {
         api::exec_with_result(client, endpoint).await
     }
 }
+
+pub mod lease {
+    use crate::{
+        api::{
+            self,
+            sys::{
+                requests::{
+                    ListLeasesRequest, ReadLeaseRequest, RenewLeaseRequest,
+                    RenewLeaseRequestBuilder, RevokeForceRequest, RevokeLeaseRequest,
+                    RevokeLeaseRequestBuilder, RevokePrefixRequest, RevokePrefixRequestBuilder,
+                    TidyLeasesRequest,
+                },
+                responses::{ListLeasesResponse, ReadLeaseResponse, RenewLeaseResponse},
+            },
+        },
+        client::Client,
+        error::ClientError,
+    };
+
+    /// Lists the lease ids under the given prefix.
+    ///
+    /// See [ListLeasesRequest]
+    #[instrument(skip(client), err)]
+    pub async fn list(
+        client: &impl Client,
+        prefix: &str,
+    ) -> Result<ListLeasesResponse, ClientError> {
+        let endpoint = ListLeasesRequest::builder().prefix(prefix).build().unwrap();
+        api::exec_with_result(client, endpoint).await
+    }
+
+    /// Reads the metadata of the given lease.
+    ///
+    /// See [ReadLeaseRequest]
+    #[instrument(skip(client), err)]
+    pub async fn read(
+        client: &impl Client,
+        lease_id: &str,
+    ) -> Result<ReadLeaseResponse, ClientError> {
+        let endpoint = ReadLeaseRequest::builder()
+            .lease_id(lease_id)
+            .build()
+            .unwrap();
+        api::exec_with_result(client, endpoint).await
+    }
+
+    /// Renews the given lease.
+    ///
+    /// See [RenewLeaseRequest]
+    #[instrument(skip(client, opts), err)]
+    pub async fn renew(
+        client: &impl Client,
+        lease_id: &str,
+        opts: Option<&mut RenewLeaseRequestBuilder>,
+    ) -> Result<RenewLeaseResponse, ClientError> {
+        let mut t = RenewLeaseRequest::builder();
+        let endpoint = opts.unwrap_or(&mut t).lease_id(lease_id).build().unwrap();
+        api::exec_with_no_result(client, endpoint).await
+    }
+
+    /// Revokes the given lease.
+    ///
+    /// See [RevokeLeaseRequest]
+    #[instrument(skip(client, opts), err)]
+    pub async fn revoke(
+        client: &impl Client,
+        lease_id: &str,
+        opts: Option<&mut RevokeLeaseRequestBuilder>,
+    ) -> Result<(), ClientError> {
+        let mut t = RevokeLeaseRequest::builder();
+        let endpoint = opts.unwrap_or(&mut t).lease_id(lease_id).build().unwrap();
+        api::exec_with_empty(client, endpoint).await
+    }
+
+    /// Revokes all leases generated under the given prefix.
+    ///
+    /// See [RevokePrefixRequest]
+    #[instrument(skip(client, opts), err)]
+    pub async fn revoke_prefix(
+        client: &impl Client,
+        prefix: &str,
+        opts: Option<&mut RevokePrefixRequestBuilder>,
+    ) -> Result<(), ClientError> {
+        let mut t = RevokePrefixRequest::builder();
+        let endpoint = opts.unwrap_or(&mut t).prefix(prefix).build().unwrap();
+        api::exec_with_empty(client, endpoint).await
+    }
+
+    /// Revokes all leases generated under the given prefix, ignoring any
+    /// errors returned by the backend. This should only be used when the
+    /// backend can no longer revoke its secrets, as it may leave them behind.
+    ///
+    /// See [RevokeForceRequest]
+    #[instrument(skip(client), err)]
+    pub async fn revoke_force(client: &impl Client, prefix: &str) -> Result<(), ClientError> {
+        let endpoint = RevokeForceRequest::builder()
+            .prefix(prefix)
+            .build()
+            .unwrap();
+        api::exec_with_empty(client, endpoint).await
+    }
+
+    /// Cleans up dangling storage entries for leases.
+    ///
+    /// See [TidyLeasesRequest]
+    #[instrument(skip(client), err)]
+    pub async fn tidy(client: &impl Client) -> Result<(), ClientError> {
+        let endpoint = TidyLeasesRequest::builder().build().unwrap();
+        api::exec_with_empty(client, endpoint).await
+    }
+}
@@ -0,0 +1,333 @@
+//! Unseals the nodes of a cluster with a set of key shares.
+//!
+//! An [Unsealer] submits key shares to every sealed node of a cluster until
+//! the threshold of each node is reached. The shares are provided by a
+//! [KeyShareSource]: [KeyShares] holds shares read upfront from files or the
+//! environment, while a closure returning a future can prompt operators for
+//! each share as it's needed. The progress of every node is reported after
+//! each submitted share and the status of the nodes, read with [sys::status],
+//! is returned once all of them were processed.
+//!
+//! ```no_run
+//! use vaultrs::client::VaultClientSettingsBuilder;
+//! use vaultrs::unsealer::{KeyShares, Unsealer};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let settings = VaultClientSettingsBuilder::default()
+//!     .addresses([
+//!         "https://vault-0:8200",
+//!         "https://vault-1:8200",
+//!         "https://vault-2:8200",
+//!     ])
+//!     .build()
+//!     .unwrap();
+//! let shares = KeyShares::from_env("VAULT_UNSEAL_KEY");
+//!
+//! let reports = Unsealer::new(settings, shares)
+//!     .on_progress(|p| println!("{}: {}/{}", p.address, p.progress, p.threshold))
+//!     .run()
+//!     .await;
+//! for report in reports {
+//!     println!("{}: {:?}", report.address, report.status);
+//! }
+//! # }
+//! ```
+
+use std::{env, fs, future::Future, path::Path};
+
+use async_trait::async_trait;
+use url::Url;
+
+use crate::{
+    api::{self, sys::requests::UnsealRequest, SecretString},
+    client::{VaultClient, VaultClientSettings},
+    error::ClientError,
+    sys::{self, ServerStatus},
+};
+
+/// A source of the key shares submitted by an [Unsealer].
+///
+/// Shares are requested from the async runtime running the [Unsealer], so a
+/// source prompting operators on the terminal should read the answer with
+/// `tokio::task::spawn_blocking` rather than blocking the runtime.
+///
+/// ```no_run
+/// use url::Url;
+/// use vaultrs::api::SecretString;
+/// use vaultrs::client::VaultClientSettingsBuilder;
+/// use vaultrs::error::ClientError;
+/// use vaultrs::unsealer::Unsealer;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let prompt = |address: &Url, index: usize| {
+///     let question = format!("Key share {} for {address}: ", index + 1);
+///     async move {
+///         tokio::task::spawn_blocking(move || {
+///             eprint!("{question}");
+///             let mut share = String::new();
+///             std::io::stdin()
+///                 .read_line(&mut share)
+///                 .map(|_| Some(SecretString::from(share.trim().to_string())))
+///                 .map_err(|e| ClientError::FileReadError {
+///                     source: e,
+///                     path: "stdin".to_string(),
+///                 })
+///         })
+///         .await
+///         .unwrap()
+///     }
+/// };
+///
+/// let settings = VaultClientSettingsBuilder::default().build().unwrap();
+/// Unsealer::new(settings, prompt).run().await;
+/// # }
+/// ```
+#[async_trait]
+pub trait KeyShareSource: Send + Sync {
+    /// Returns the key share with the given index to submit to the node with
+    /// the given address, or `None` once no shares are left.
+    ///
+    /// Shares are requested in order starting from index zero for every node,
+    /// and only until the node is unsealed.
+    async fn share(&self, address: &Url, index: usize)
+        -> Result<Option<SecretString>, ClientError>;
+}
+
+#[async_trait]
+impl<F, Fut> KeyShareSource for F
+where
+    F: Fn(&Url, usize) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<Option<SecretString>, ClientError>> + Send,
+{
+    async fn share(
+        &self,
+        address: &Url,
+        index: usize,
+    ) -> Result<Option<SecretString>, ClientError> {
+        self(address, index).await
+    }
+}
+
+/// Key shares which are known upfront, submitted in order to every node.
+#[derive(Clone, Debug, Default)]
+pub struct KeyShares {
+    shares: Vec<SecretString>,
+}
+
+impl KeyShares {
+    /// Creates a source from the given key shares.
+    pub fn new<T: Into<SecretString>>(shares: impl IntoIterator<Item = T>) -> Self {
+        KeyShares {
+            shares: shares.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Reads the key shares from the given files, which contain one key share
+    /// per line.
+    pub fn from_files<P: AsRef<Path>>(
+        paths: impl IntoIterator<Item = P>,
+    ) -> Result<Self, ClientError> {
+        let mut shares = Vec::new();
+        for path in paths {
+            let path = path.as_ref();
+            let content = SecretString::from(fs::read_to_string(path).map_err(|e| {
+                ClientError::FileReadError {
+                    source: e,
+                    path: path.display().to_string(),
+                }
+            })?);
+            shares.extend(
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty())
+                    .map(SecretString::from),
+            );
+        }
+        Ok(KeyShares { shares })
+    }
+
+    /// Reads the key shares from the environment variables named after the
+    /// given prefix followed by an index starting at one, e.g.
+    /// `VAULT_UNSEAL_KEY_1`, `VAULT_UNSEAL_KEY_2`, ... for the
+    /// `VAULT_UNSEAL_KEY` prefix. Reading stops at the first variable which
+    /// isn't set.
+    pub fn from_env(prefix: &str) -> Self {
+        let shares = (1..)
+            .map_while(|i| env::var(format!("{prefix}_{i}")).ok())
+            .map(SecretString::from)
+            .collect();
+        KeyShares { shares }
+    }
+
+    /// Returns the number of key shares.
+    pub fn len(&self) -> usize {
+        self.shares.len()
+    }
+
+    /// Returns `true` if there are no key shares.
+    pub fn is_empty(&self) -> bool {
+        self.shares.is_empty()
+    }
+}
+
+#[async_trait]
+impl KeyShareSource for KeyShares {
+    async fn share(&self, _: &Url, index: usize) -> Result<Option<SecretString>, ClientError> {
+        Ok(self.shares.get(index).cloned())
+    }
+}
+
+/// The progress of unsealing a node, reported after each submitted key share.
+#[derive(Clone, Debug)]
+pub struct UnsealProgress {
+    /// The address of the node.
+    pub address: Url,
+    /// Whether the node is still sealed.
+    pub sealed: bool,
+    /// The number of key shares submitted so far.
+    pub progress: u64,
+    /// The number of key shares required to unseal the node.
+    pub threshold: u64,
+}
+
+/// The outcome of unsealing a node.
+#[derive(Debug)]
+pub struct NodeReport {
+    /// The address of the node.
+    pub address: Url,
+    /// The status of the node after the key shares were submitted, or the
+    /// error which stopped the node from being unsealed. A node which is
+    /// still [sealed][ServerStatus::SEALED] ran out of key shares.
+    pub status: Result<ServerStatus, ClientError>,
+    /// The last progress reported for the node, `None` if no key share was
+    /// submitted to it.
+    pub progress: Option<UnsealProgress>,
+}
+
+type ProgressCallback = Box<dyn Fn(&UnsealProgress) + Send + Sync>;
+
+/// Submits key shares to the sealed nodes of a cluster until they're
+/// unsealed.
+///
+/// The nodes are unsealed one after the other. Nodes which are already
+/// unsealed, or not initialized, are left untouched.
+pub struct Unsealer<S: KeyShareSource> {
+    settings: VaultClientSettings,
+    shares: S,
+    reset: bool,
+    migrate: bool,
+    on_progress: Option<ProgressCallback>,
+}
+
+impl<S: KeyShareSource> Unsealer<S> {
+    /// Creates an unsealer for the nodes of the given settings, which are the
+    /// addresses given to `VaultClientSettingsBuilder::addresses` or the
+    /// address of the settings.
+    pub fn new(settings: VaultClientSettings, shares: S) -> Self {
+        Unsealer {
+            settings,
+            shares,
+            reset: false,
+            migrate: false,
+            on_progress: None,
+        }
+    }
+
+    /// Sets whether the progress of previous unseal attempts is discarded
+    /// before submitting the first key share to a node.
+    pub fn reset(mut self, reset: bool) -> Self {
+        self.reset = reset;
+        self
+    }
+
+    /// Sets whether the key shares are submitted for a seal migration.
+    pub fn migrate(mut self, migrate: bool) -> Self {
+        self.migrate = migrate;
+        self
+    }
+
+    /// Sets a function called with the progress of a node after each key
+    /// share submitted to it.
+    pub fn on_progress(mut self, f: impl Fn(&UnsealProgress) + Send + Sync + 'static) -> Self {
+        self.on_progress = Some(Box::new(f));
+        self
+    }
+
+    /// Returns the addresses of the nodes to unseal.
+    pub fn addresses(&self) -> Vec<Url> {
+        match &self.settings.failover {
+            Some(failover) => failover.addresses().to_vec(),
+            None => vec![self.settings.address.clone()],
+        }
+    }
+
+    /// Unseals every node, returning a report for each of them in the order
+    /// of their addresses.
+    pub async fn run(&self) -> Vec<NodeReport> {
+        let mut reports = Vec::new();
+        for address in self.addresses() {
+            let mut progress = None;
+            let status = self.unseal_node(&address, &mut progress).await;
+            if let Err(e) = &status {
+                warn!("Failed unsealing {}: {}", address, e);
+            }
+            reports.push(NodeReport {
+                address,
+                status,
+                progress,
+            });
+        }
+        reports
+    }
+
+    async fn unseal_node(
+        &self,
+        address: &Url,
+        progress: &mut Option<UnsealProgress>,
+    ) -> Result<ServerStatus, ClientError> {
+        let mut settings = self.settings.clone();
+        settings.address = address.clone();
+        settings.failover = None;
+        settings.consistency = None;
+        let client = VaultClient::new(settings)?;
+
+        let status = sys::status(&client).await?;
+        if !matches!(status, ServerStatus::SEALED) {
+            debug!("Skipping {} with status {:?}", address, status);
+            return Ok(status);
+        }
+
+        if self.reset {
+            sys::unseal(&client, None, Some(true), None).await?;
+        }
+        let migrate = self.migrate.then_some(true);
+        let mut index = 0;
+        while let Some(share) = self.shares.share(address, index).await? {
+            let endpoint = UnsealRequest::builder()
+                .key(Some(share))
+                .migrate(migrate)
+                .build()
+                .unwrap();
+            let resp = api::exec_with_no_result(&client, endpoint).await?;
+            let current = UnsealProgress {
+                address: address.clone(),
+                sealed: resp.sealed,
+                progress: resp.progress,
+                threshold: resp.threshold,
+            };
+            if let Some(f) = &self.on_progress {
+                f(&current);
+            }
+            *progress = Some(current);
+            if !resp.sealed {
+                break;
+            }
+            index += 1;
+        }
+
+        sys::status(&client).await
+    }
+}